@@ -1,6 +1,10 @@
+#[cfg(feature = "rayon")]
+use criterion::BatchSize;
 use criterion::{criterion_group, criterion_main, Criterion};
 use rand::{rngs::SmallRng, thread_rng, SeedableRng};
 use set_genome::{activations::Activation, Genome, Mutations, Parameters};
+#[cfg(feature = "rayon")]
+use set_genome::{CompatibilityDistance, CrossoverStrategy};
 
 pub fn crossover_same_genome_benchmark(c: &mut Criterion) {
     let parameters = Parameters::default();
@@ -34,6 +38,7 @@ pub fn crossover_highly_mutated_genomes_benchmark(c: &mut Criterion) {
             },
             Mutations::AddConnection { chance: 1.0 },
         ],
+        ..Default::default()
     };
 
     let mut genome_0 = Genome::initialized(&parameters);
@@ -70,6 +75,7 @@ pub fn mutate_genome_benchmark(c: &mut Criterion) {
             },
             Mutations::AddConnection { chance: 1.0 },
         ],
+        ..Default::default()
     };
 
     let mut genome = Genome::initialized(&parameters);
@@ -86,6 +92,106 @@ pub fn add_node_to_genome_benchmark(c: &mut Criterion) {
     });
 }
 
+#[cfg(feature = "rayon")]
+pub fn mutate_batch_benchmark(c: &mut Criterion) {
+    let parameters = Parameters::default();
+    let genomes = vec![Genome::initialized(&parameters); 1000];
+
+    let mut group = c.benchmark_group("mutate 1000 genomes");
+    group.bench_function("sequential", |b| {
+        let rng = &mut SmallRng::seed_from_u64(42);
+        b.iter_batched_ref(
+            || genomes.clone(),
+            |genomes| {
+                for genome in genomes.iter_mut() {
+                    let _ = genome.mutate_with_rng(&parameters, rng);
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("parallel", |b| {
+        b.iter_batched_ref(
+            || genomes.clone(),
+            |genomes| Genome::mutate_batch(genomes, &parameters, 42),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+#[cfg(feature = "rayon")]
+pub fn offspring_batch_benchmark(c: &mut Criterion) {
+    let parameters = Parameters::default();
+    let mut parents = vec![Genome::initialized(&parameters); 100];
+    for _ in 0..100 {
+        Genome::mutate_batch(&mut parents, &parameters, 42);
+    }
+    let pairs = (0..1000)
+        .map(|i| (i % 100, (i * 7) % 100))
+        .collect::<Vec<_>>();
+    let strategy = CrossoverStrategy::default();
+
+    let mut group = c.benchmark_group("1000 offspring");
+    group.bench_function("sequential", |b| {
+        let rng = &mut SmallRng::seed_from_u64(42);
+        b.iter(|| {
+            pairs
+                .iter()
+                .map(|&(fitter, other)| {
+                    let mut offspring =
+                        parents[fitter].cross_in_with_strategy(&parents[other], &strategy, rng);
+                    let _ = offspring.mutate_with_rng(&parameters, rng);
+                    offspring
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| Genome::offspring_batch(&parents, &pairs, &strategy, &parameters, 42))
+    });
+    group.finish();
+}
+
+#[cfg(feature = "rayon")]
+pub fn distance_matrix_benchmark(c: &mut Criterion) {
+    let parameters = Parameters::default();
+    let mut genomes = vec![Genome::initialized(&parameters); 300];
+    for _ in 0..50 {
+        Genome::mutate_batch(&mut genomes, &parameters, 42);
+    }
+    let distance = CompatibilityDistance::from_parameters(&parameters);
+
+    let mut group = c.benchmark_group("distance matrix of 300 genomes");
+    // same upper triangle as the parallel version
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            genomes
+                .iter()
+                .enumerate()
+                .map(|(row, genome)| {
+                    genomes[row + 1..]
+                        .iter()
+                        .map(|other| distance.between(genome, other))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| distance.distance_matrix(&genomes))
+    });
+    group.finish();
+}
+
+#[cfg(feature = "rayon")]
+criterion_group!(
+    parallel_benches,
+    mutate_batch_benchmark,
+    offspring_batch_benchmark,
+    distance_matrix_benchmark
+);
+
 criterion_group!(
     benches,
     mutate_genome_benchmark,
@@ -93,4 +199,7 @@ criterion_group!(
     crossover_highly_mutated_genomes_benchmark,
     add_node_to_genome_benchmark
 );
+#[cfg(not(feature = "rayon"))]
 criterion_main!(benches);
+#[cfg(feature = "rayon")]
+criterion_main!(benches, parallel_benches);
@@ -1,7 +1,12 @@
-use favannat::network::{EdgeLike, NetworkLike, NodeLike, Recurrent};
+use std::collections::HashMap;
+
+use favannat::network::{
+    net::{Edge, Net, Node as NetNode},
+    EdgeLike, NodeLike,
+};
 
 use crate::{
-    genes::{activations, Activation, Connection, Node},
+    genes::{Connection, Id, Node},
     genome::Genome,
 };
 
@@ -10,19 +15,7 @@ impl NodeLike for Node {
         self.id.0 as usize
     }
     fn activation(&self) -> fn(f64) -> f64 {
-        match self.activation {
-            Activation::Linear => activations::LINEAR,
-            Activation::Sigmoid => activations::SIGMOID,
-            Activation::Gaussian => activations::GAUSSIAN,
-            Activation::Tanh => activations::TANH,
-            Activation::Step => activations::STEP,
-            Activation::Sine => activations::SINE,
-            Activation::Cosine => activations::COSINE,
-            Activation::Inverse => activations::INVERSE,
-            Activation::Absolute => activations::ABSOLUTE,
-            Activation::Relu => activations::RELU,
-            Activation::Squared => activations::SQUARED,
-        }
+        self.activation.function()
     }
 }
 
@@ -38,36 +31,208 @@ impl EdgeLike for Connection {
     }
 }
 
-impl NetworkLike<Node, Connection> for Genome {
-    fn nodes(&self) -> Vec<&Node> {
-        self.nodes().collect()
-    }
-    fn edges(&self) -> Vec<&Connection> {
-        self.feed_forward.as_sorted_vec()
-    }
-    fn inputs(&self) -> Vec<&Node> {
-        self.inputs.as_sorted_vec()
-    }
-    fn outputs(&self) -> Vec<&Node> {
-        self.outputs.as_sorted_vec()
-    }
-    fn hidden(&self) -> Vec<&Node> {
-        self.hidden.as_sorted_vec()
-    }
-}
+impl Genome {
+    /// Translates the genome into a [`Net`] which can be fabricated by any favannat fabricator.
+    ///
+    /// This is the only way to hand a genome to favannat.
+    /// The `bias` and `response` of a node can not be expressed by the genes alone, so [`Genome`] itself is not [`favannat::network::NetworkLike`].
+    ///
+    /// favannat expects a plain function per node, so node parameters are folded into the edges:
+    /// the `response` of a node scales all its incoming weights and its `bias` becomes an incoming edge from an additional constant node.
+    /// Disabled connections are left out.
+    /// The bias node, if present, is the last input and needs to be supplied with a constant one.
+    /// A genome without any inputs can not feed the constant node, so favannat rejects it as soon as any node has a bias.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use favannat::{Evaluator, Fabricator, MatrixFeedforwardFabricator};
+    /// use set_genome::{Genome, Parameters};
+    ///
+    /// let genome = Genome::initialized(&Parameters::basic(3, 2));
+    ///
+    /// let network = MatrixFeedforwardFabricator::fabricate(&genome.favannat_net()).unwrap();
+    ///
+    /// assert_eq!(network.evaluate(vec![0.0, 1.0, 2.0]).len(), 2);
+    /// ```
+    pub fn favannat_net(&self) -> Net {
+        let inputs = self
+            .inputs
+            .as_ordered_vec()
+            .into_iter()
+            .chain(self.bias.iter())
+            .collect::<Vec<_>>();
+        let hidden = self.hidden.as_ordered_vec();
+        let outputs = self.outputs.as_ordered_vec();
+
+        // favannat orders inputs and outputs by id, so ids follow their order
+        let index: HashMap<Id, usize> = inputs
+            .iter()
+            .chain(hidden.iter())
+            .chain(outputs.iter())
+            .enumerate()
+            .map(|(index, node)| (node.id, index))
+            .collect();
+        let constant = index.len();
+
+        let computed = |node: &&Node| NetNode::new(index[&node.id], node.activation.function());
+        let response: HashMap<Id, f64> = hidden
+            .iter()
+            .chain(outputs.iter())
+            .map(|node| (node.id, node.response))
+            .collect();
+        let edge = |connection: &Connection| {
+            Edge::new(
+                index[&connection.input],
+                index[&connection.output],
+                connection.weight * response.get(&connection.output).unwrap_or(&1.0),
+            )
+        };
+
+        let mut edges = self
+            .feed_forward
+            .as_sorted_vec()
+            .into_iter()
+            .filter(|connection| connection.enabled)
+            .map(edge)
+            .collect::<Vec<_>>();
+
+        let biased = hidden
+            .iter()
+            .chain(outputs.iter())
+            .filter(|node| node.bias != 0.0)
+            .collect::<Vec<_>>();
+
+        let mut nodes = inputs.iter().map(computed).collect::<Vec<_>>();
+        nodes.extend(hidden.iter().map(computed));
+
+        if !biased.is_empty() {
+            // the constant node needs an input to be computable, its value does not matter
+            if !inputs.is_empty() {
+                edges.push(Edge::new(0, constant, 0.0));
+            }
+            edges.extend(
+                biased
+                    .into_iter()
+                    .map(|node| Edge::new(constant, index[&node.id], node.bias)),
+            );
+            nodes.push(NetNode::new(constant, |_| 1.0));
+        }
+
+        nodes.extend(outputs.iter().map(computed));
+
+        let mut net = Net::new(inputs.len(), outputs.len(), nodes, edges);
 
-impl Recurrent<Node, Connection> for Genome {
-    fn recurrent_edges(&self) -> Vec<&Connection> {
-        self.recurrent.as_sorted_vec()
+        net.set_recurrent_edges(
+            self.recurrent
+                .as_sorted_vec()
+                .into_iter()
+                .filter(|connection| connection.enabled)
+                .map(edge)
+                .collect(),
+        );
+
+        net
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use favannat::{MatrixRecurrentFabricator, StatefulEvaluator, StatefulFabricator};
+    use favannat::{
+        network::{EdgeLike, NetworkLike},
+        Evaluator, Fabricator, MatrixFeedforwardFabricator, MatrixRecurrentFabricator,
+        StatefulEvaluator, StatefulFabricator,
+    };
     use rand_distr::{Distribution, Uniform};
 
-    use crate::{activations::Activation, Genome, Mutations, Parameters, Structure};
+    use crate::{
+        activations::Activation,
+        genes::{Connection, Genes, Id, Node},
+        BoundaryHandling, Genome, Mutations, Parameters, PerturbationDistribution, Structure,
+    };
+
+    #[test]
+    fn bias_is_last_input() {
+        let genome = Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            bias: Some(Node::bias(Id(2))),
+            outputs: Genes(
+                [Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [
+                    Connection::new(Id(0), 1.0, Id(1)),
+                    Connection::new(Id(2), 0.5, Id(1)),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            ..Default::default()
+        };
+
+        let network =
+            MatrixFeedforwardFabricator::fabricate(&genome.favannat_net()).expect("not okay");
+
+        assert!((network.evaluate(vec![2.0, 1.0])[0] - 2.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn fold_node_parameters_into_edges() {
+        let genome = Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            outputs: Genes(
+                [Node {
+                    bias: 1.0,
+                    response: 0.5,
+                    ..Node::output(Id(1), 0, Activation::Linear)
+                }]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            feed_forward: Genes(
+                [Connection::new(Id(0), 2.0, Id(1))]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+
+        let network =
+            MatrixFeedforwardFabricator::fabricate(&genome.favannat_net()).expect("not okay");
+
+        // 1 + 0.5 * 3 * 2
+        assert!((network.evaluate(vec![3.0])[0] - 4.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn keep_bias_without_inputs() {
+        let genome = Genome {
+            outputs: Genes(
+                [Node {
+                    bias: 1.0,
+                    ..Node::output(Id(1), 0, Activation::Linear)
+                }]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            ..Default::default()
+        };
+
+        let net = genome.favannat_net();
+
+        assert_eq!(net.nodes().len(), 2);
+        assert!(net
+            .edges()
+            .iter()
+            .any(|edge| edge.end() == 0 && edge.weight() == 1.0));
+    }
 
     // This test brakes with favannat version 0.6.1 due to a bug there. Now with favannat 0.6.2 it is fine.
     #[test]
@@ -79,12 +244,15 @@ mod tests {
                 percent_of_connected_inputs: 1.0,
                 outputs_activation: Activation::Sigmoid,
                 seed: 42,
+                bias: false,
             },
             mutations: vec![
                 Mutations::ChangeWeights {
                     chance: 0.8,
                     percent_perturbed: 0.5,
                     standard_deviation: 0.2,
+                    distribution: PerturbationDistribution::Normal,
+                    boundary_handling: BoundaryHandling::Reflect,
                 },
                 Mutations::AddNode {
                     chance: 0.1,
@@ -107,15 +275,17 @@ mod tests {
                 Mutations::RemoveConnection { chance: 0.01 },
                 Mutations::RemoveNode { chance: 0.05 },
             ],
+            ..Default::default()
         };
 
         let mut genome = Genome::initialized(&parameters);
 
         for _ in 0..100 {
-            genome.mutate(&parameters);
+            let _ = genome.mutate(&parameters);
         }
 
-        let mut evaluator = MatrixRecurrentFabricator::fabricate(&genome).expect("not okay");
+        let mut evaluator =
+            MatrixRecurrentFabricator::fabricate(&genome.favannat_net()).expect("not okay");
 
         let between = Uniform::from(-10000.0..10000.0);
         let mut rng = rand::thread_rng();
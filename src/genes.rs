@@ -2,9 +2,12 @@
 //!
 //! The genome holds several fields with `Genes` of different types.
 
-use rand::{prelude::IteratorRandom, prelude::SliceRandom, Rng};
+use rand::{
+    distributions::WeightedIndex, prelude::Distribution, prelude::IteratorRandom,
+    prelude::SliceRandom, Rng,
+};
 use seahash::SeaHasher;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use std::{
     collections::HashSet,
     hash::{BuildHasher, Hash, Hasher},
@@ -39,9 +42,16 @@ impl BuildHasher for GeneHasher {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
 pub struct Genes<T: Gene>(pub HashSet<T, GeneHasher>);
 
+// serialize in sorted order so identical genes always produce identical output
+impl<T: Gene + Ord + Serialize> Serialize for Genes<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.as_sorted_vec())
+    }
+}
+
 // see here: https://stackoverflow.com/questions/60882381/what-is-the-fastest-correct-way-to-detect-that-there-are-no-duplicates-in-a-json/60884343#60884343
 impl<T: Gene> Hash for Genes<T> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -98,6 +108,21 @@ impl<T: Gene> Genes<T> {
     pub fn iterate_unique_genes<'a>(&'a self, other: &'a Genes<T>) -> impl Iterator<Item = &'a T> {
         self.symmetric_difference(other)
     }
+
+    /// Iterates all genes of `self` together with the matching genes of every one of `others` that carries them, paired with the position in `others`.
+    pub fn iterate_matching_genes_many<'a>(
+        &'a self,
+        others: &'a [&'a Genes<T>],
+    ) -> impl Iterator<Item = (&'a T, Vec<(usize, &'a T)>)> {
+        self.iter().map(move |item_self| {
+            let matching = others
+                .iter()
+                .enumerate()
+                .filter_map(|(index, other)| other.get(item_self).map(|item| (index, item)))
+                .collect();
+            (item_self, matching)
+        })
+    }
 }
 
 impl<T: Gene> FromIterator<T> for Genes<T> {
@@ -114,6 +139,15 @@ impl<T: Gene + Ord> Genes<T> {
     }
 }
 
+impl Genes<Node> {
+    /// Sorts the nodes by `order`, then by `id`, i.e. inputs and outputs by their position and hidden nodes by their depth.
+    pub fn as_ordered_vec(&self) -> Vec<&Node> {
+        let mut vec: Vec<&Node> = self.iter().collect();
+        vec.sort_unstable_by_key(|node| (node.order, node.id));
+        vec
+    }
+}
+
 impl<T: Gene + Clone> Genes<T> {
     pub fn cross_in(&self, other: &Self, rng: &mut impl Rng) -> Self {
         self.iterate_matching_genes(other)
@@ -127,4 +161,26 @@ impl<T: Gene + Clone> Genes<T> {
             .chain(self.difference(other).cloned())
             .collect()
     }
+
+    /// Keeps all genes of `self` and takes the attributes of every gene from one of the genomes carrying it.
+    ///
+    /// `weights` holds the selection weight of `self` followed by those of `others`, only carriers of a gene compete for it.
+    /// If all carriers have a weight of zero the gene is taken from `self`.
+    pub fn cross_in_many(&self, others: &[&Self], weights: &[f64], rng: &mut impl Rng) -> Self {
+        self.iterate_matching_genes_many(others)
+            .map(|(gene_self, matching)| {
+                let candidates = Some(weights[0])
+                    .into_iter()
+                    .chain(matching.iter().map(|&(index, _)| weights[index + 1]));
+                let chosen = WeightedIndex::new(candidates)
+                    .map(|distribution| distribution.sample(rng))
+                    .unwrap_or(0);
+                if chosen == 0 {
+                    gene_self.clone()
+                } else {
+                    gene_self.recombine(matching[chosen - 1].1)
+                }
+            })
+            .collect()
+    }
 }
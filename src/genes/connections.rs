@@ -7,25 +7,37 @@ use std::{
 };
 
 use super::{Gene, Id};
+use crate::parameters::{BoundaryHandling, PerturbationDistribution, WeightBounds};
 
 /// Struct describing a ANN connection.
 ///
 /// A connection is characterised by its input/origin/start, its output/destination/end and its weight.
+/// Disabled connections are kept in the genome but do not propagate any signal.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Connection {
     pub input: Id,
     pub output: Id,
     pub weight: f64,
     pub id_counter: u64,
+    #[serde(default = "enabled")]
+    pub enabled: bool,
+}
+
+fn enabled() -> bool {
+    true
 }
 
 impl Connection {
+    /// Chance for a connection disabled in either parent to be disabled in the offspring, as proposed by NEAT.
+    pub const INHERIT_DISABLED_CHANCE: f64 = 0.75;
+
     pub fn new(input: Id, weight: f64, output: Id) -> Self {
         Self {
             input,
             output,
             weight,
             id_counter: 0,
+            enabled: true,
         }
     }
 
@@ -42,19 +54,64 @@ impl Connection {
         Id(id_hasher.finish())
     }
 
+    /// Perturbs the weight by a normal sample, clamped to the default [`WeightBounds`], see [`Connection::perturb_weight_with`].
     pub fn perturb_weight(&mut self, standard_deviation: f64, rng: &mut impl Rng) {
         self.weight = Self::weight_perturbation(self.weight, standard_deviation, rng);
     }
 
+    pub fn perturb_weight_with(
+        &mut self,
+        distribution: PerturbationDistribution,
+        standard_deviation: f64,
+        boundary_handling: BoundaryHandling,
+        bounds: &WeightBounds,
+        rng: &mut impl Rng,
+    ) {
+        self.weight = Self::weight_perturbation_with(
+            self.weight,
+            distribution,
+            standard_deviation,
+            boundary_handling,
+            bounds,
+            rng,
+        );
+    }
+
+    /// Adds a normal sample to `weight`, clamped to the default [`WeightBounds`], see [`Connection::weight_perturbation_with`].
     pub fn weight_perturbation(weight: f64, standard_deviation: f64, rng: &mut impl Rng) -> f64 {
-        // approximatly normal distributed sample, see: https://en.wikipedia.org/wiki/Irwin%E2%80%93Hall_distribution#Approximating_a_Normal_distribution
-        let mut perturbation =
-            ((0..12).map(|_| rng.gen::<f64>()).sum::<f64>() - 6.0) * standard_deviation;
+        Self::weight_perturbation_with(
+            weight,
+            PerturbationDistribution::Normal,
+            standard_deviation,
+            BoundaryHandling::Clamp,
+            &WeightBounds::default(),
+            rng,
+        )
+    }
+
+    /// Adds a sample of `distribution` to `weight`, keeping the result within `bounds` via `boundary_handling`.
+    pub fn weight_perturbation_with(
+        weight: f64,
+        distribution: PerturbationDistribution,
+        standard_deviation: f64,
+        boundary_handling: BoundaryHandling,
+        bounds: &WeightBounds,
+        rng: &mut impl Rng,
+    ) -> f64 {
+        let weight = bounds.clamp(weight);
+
+        boundary_handling.apply(bounds, || {
+            weight + distribution.sample(standard_deviation, rng)
+        })
+    }
 
-        while (weight + perturbation) > 1.0 || (weight + perturbation) < -1.0 {
-            perturbation = -perturbation / 2.0;
+    /// Decides whether a connection present in both parents is enabled in the offspring.
+    pub fn inherit_enabled(&self, other: &Self, rng: &mut impl Rng) -> bool {
+        if self.enabled && other.enabled {
+            true
+        } else {
+            rng.gen::<f64>() >= Self::INHERIT_DISABLED_CHANCE
         }
-        weight + perturbation
     }
 }
 
@@ -15,12 +15,25 @@ pub mod activations;
 ///
 /// A node is made up of an identifier and activation function.
 /// See [`Activations`] for more information.
+///
+/// Hidden and output nodes compute `activation(bias + response * sum_of_inputs)`, `bias` and `response` are meaningless for input and bias nodes.
+///
+/// Nodes compare, hash and sort by `id` only, `Genes::as_ordered_vec` sorts them by `order`, then by `id`.
+/// Inputs and outputs are ordered by their position, hidden nodes by their feed-forward depth, see [`crate::Genome::order_hidden_nodes`].
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Node {
     pub id: Id,
     pub order: usize,
     pub activation: Activation,
     pub id_counter: u64,
+    #[serde(default)]
+    pub bias: f64,
+    #[serde(default = "neutral_response")]
+    pub response: f64,
+}
+
+fn neutral_response() -> f64 {
+    1.0
 }
 
 impl Node {
@@ -30,6 +43,8 @@ impl Node {
             order,
             activation: Activation::Linear,
             id_counter: 0,
+            bias: 0.0,
+            response: 1.0,
         }
     }
 
@@ -39,6 +54,20 @@ impl Node {
             order,
             activation,
             id_counter: 0,
+            bias: 0.0,
+            response: 1.0,
+        }
+    }
+
+    /// The bias node always emits a constant signal of one and sorts after every input node.
+    pub fn bias(id: Id) -> Self {
+        Node {
+            id,
+            order: usize::MAX,
+            activation: Activation::Linear,
+            id_counter: 0,
+            bias: 0.0,
+            response: 1.0,
         }
     }
 
@@ -48,6 +77,8 @@ impl Node {
             order: 0,
             activation,
             id_counter: 0,
+            bias: 0.0,
+            response: 1.0,
         }
     }
 
@@ -64,6 +95,8 @@ impl Gene for Node {
     fn recombine(&self, other: &Self) -> Self {
         Self {
             activation: other.activation,
+            bias: other.bias,
+            response: other.response,
             ..*self
         }
     }
@@ -91,6 +124,6 @@ impl PartialOrd for Node {
 
 impl Ord for Node {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.order.cmp(&other.order)
+        self.id.cmp(&other.id)
     }
 }
@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// [actual functions listed here]: ../activations/index.html#constants
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Activation {
     Linear,
     Sigmoid,
@@ -40,6 +41,23 @@ impl Activation {
             Self::Squared,
         ]
     }
+
+    /// Returns the constant function matching the variant.
+    pub fn function(&self) -> fn(f64) -> f64 {
+        match self {
+            Self::Linear => LINEAR,
+            Self::Sigmoid => SIGMOID,
+            Self::Tanh => TANH,
+            Self::Gaussian => GAUSSIAN,
+            Self::Step => STEP,
+            Self::Sine => SINE,
+            Self::Cosine => COSINE,
+            Self::Inverse => INVERSE,
+            Self::Absolute => ABSOLUTE,
+            Self::Relu => RELU,
+            Self::Squared => SQUARED,
+        }
+    }
 }
 
 /// Returns the argument unchanged.
@@ -5,16 +5,34 @@ use std::{
 
 use crate::{
     genes::{Connection, Genes, Id, Node},
-    parameters::Structure,
+    parameters::{Structure, WeightConfig},
 };
 
 use rand::{rngs::SmallRng, seq::SliceRandom, thread_rng, Rng, SeedableRng};
 use seahash::SeaHasher;
 use serde::{Deserialize, Serialize};
 
+mod adjacency;
+#[cfg(feature = "rayon")]
+mod batch;
 mod compatibility_distance;
-
-pub use compatibility_distance::CompatibilityDistance;
+mod crossover;
+mod depth;
+mod diff;
+mod dot;
+mod prune;
+mod validation;
+
+pub use adjacency::{Adjacency, AdjacencyError, SparseMatrix};
+pub use compatibility_distance::{
+    CompatibilityDistance, CompatibilityFactors, DistanceBreakdown, Normalization,
+};
+pub use crossover::CrossoverStrategy;
+pub use depth::Layers;
+pub use diff::{ActivationChange, EnabledChange, GenomeDiff, NodeParameterChange, WeightChange};
+pub use dot::{DotOptions, NodeIds, RankDirection};
+pub use prune::{PruneOptions, PruneSummary};
+pub use validation::GenomeViolation;
 
 /// This is the core data structure this crate revoles around.
 ///
@@ -26,6 +44,8 @@ pub use compatibility_distance::CompatibilityDistance;
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Genome {
     pub inputs: Genes<Node>,
+    #[serde(default)]
+    pub bias: Option<Node>,
     pub hidden: Genes<Node>,
     pub outputs: Genes<Node>,
     pub feed_forward: Genes<Connection>,
@@ -44,23 +64,32 @@ impl Genome {
 
         let mut rng = SmallRng::seed_from_u64(seed_hasher.finish());
 
+        let inputs = (0..structure.number_of_inputs)
+            .map(|order| Node::input(Id(rng.gen::<u64>()), order))
+            .collect();
+        let outputs = (0..structure.number_of_outputs)
+            .map(|order| Node::output(Id(rng.gen::<u64>()), order, structure.outputs_activation))
+            .collect();
+        // drawn last so the input and output ids do not depend on the presence of a bias node
+        let bias = if structure.bias {
+            Some(Node::bias(Id(rng.gen::<u64>())))
+        } else {
+            None
+        };
+
         Genome {
-            inputs: (0..structure.number_of_inputs)
-                .map(|order| Node::input(Id(rng.gen::<u64>()), order))
-                .collect(),
-            outputs: (0..structure.number_of_outputs)
-                .map(|order| {
-                    Node::output(Id(rng.gen::<u64>()), order, structure.outputs_activation)
-                })
-                .collect(),
+            inputs,
+            bias,
+            outputs,
             ..Default::default()
         }
     }
 
-    /// Returns an iterator over references to all node genes (input + hidden + output) in the genome.
+    /// Returns an iterator over references to all node genes (input + bias + hidden + output) in the genome.
     pub fn nodes(&self) -> impl Iterator<Item = &Node> {
         self.inputs
             .iter()
+            .chain(self.bias.iter())
             .chain(self.hidden.iter())
             .chain(self.outputs.iter())
     }
@@ -68,6 +97,7 @@ impl Genome {
     pub fn contains(&self, id: Id) -> bool {
         let fake_node = &Node::input(id, 0);
         self.inputs.contains(fake_node)
+            || self.bias.as_ref() == Some(fake_node)
             || self.hidden.contains(fake_node)
             || self.outputs.contains(fake_node)
     }
@@ -78,21 +108,50 @@ impl Genome {
     }
 
     /// Initializes a genome, i.e. connects the in the [`Structure`] configured percent of inputs to all outputs by creating connection genes with random weights.
+    /// A bias node, if present, is always connected to all outputs.
     pub fn init(&mut self, structure: &Structure) {
-        let rng = &mut SmallRng::from_rng(thread_rng()).unwrap();
+        self.init_with_rng(structure, &mut SmallRng::from_rng(thread_rng()).unwrap())
+    }
+
+    /// Same as [`Genome::init`] but draws all randomness from the supplied `rng`.
+    pub fn init_with_rng(&mut self, structure: &Structure, rng: &mut impl Rng) {
+        self.init_with_weights(structure, &WeightConfig::default(), rng)
+    }
 
+    /// Same as [`Genome::init_with_rng`] but samples the weights according to `weights`, usually [`crate::Parameters::weights`].
+    pub fn init_with_weights(
+        &mut self,
+        structure: &Structure,
+        weights: &WeightConfig,
+        rng: &mut impl Rng,
+    ) {
         let mut possible_inputs = self.inputs.iter().collect::<Vec<_>>();
         possible_inputs.shuffle(rng);
 
-        for input in possible_inputs.iter().take(
-            (structure.percent_of_connected_inputs * structure.number_of_inputs as f64).ceil()
-                as usize,
-        ) {
+        let connected_inputs = (structure.percent_of_connected_inputs
+            * structure.number_of_inputs as f64)
+            .ceil() as usize;
+
+        // every output receives a connection from each connected input and the bias
+        let fan_in = connected_inputs + self.bias.iter().count();
+
+        for input in possible_inputs.iter().take(connected_inputs) {
             // connect to every output
             for output in self.outputs.iter() {
                 assert!(self.feed_forward.insert(Connection::new(
                     input.id,
-                    Connection::weight_perturbation(0.0, 0.1, rng),
+                    weights.initial_weight(fan_in, rng),
+                    output.id
+                )));
+            }
+        }
+
+        // the bias node is always connected to every output
+        if let Some(bias) = &self.bias {
+            for output in self.outputs.iter() {
+                assert!(self.feed_forward.insert(Connection::new(
+                    bias.id,
+                    weights.initial_weight(fan_in, rng),
                     output.id
                 )));
             }
@@ -109,29 +168,6 @@ impl Genome {
         self.feed_forward.is_empty() && self.recurrent.is_empty()
     }
 
-    /// Cross-in another genome.
-    /// For connection genes present in both genomes flip a coin to determine the weight inside the new genome.
-    /// For node genes present in both genomes flip a coin to determine the activation function inside the new genome.
-    /// Any structure not present in other is taken over unchanged from `self`.
-    pub fn cross_in(&self, other: &Self) -> Self {
-        // Instantiating an RNG for every call might slow things down.
-        let mut rng = SmallRng::from_rng(thread_rng()).unwrap();
-
-        let feed_forward = self.feed_forward.cross_in(&other.feed_forward, &mut rng);
-        let recurrent = self.recurrent.cross_in(&other.recurrent, &mut rng);
-        let hidden = self.hidden.cross_in(&other.hidden, &mut rng);
-
-        Genome {
-            feed_forward,
-            recurrent,
-            hidden,
-            // use input and outputs from fitter, but they should be identical with weaker
-            inputs: self.inputs.clone(),
-            outputs: self.outputs.clone(),
-            ..Default::default()
-        }
-    }
-
     /// Check if connecting `start_node` and `end_node` would introduce a circle into the ANN structure.
     /// Think about the ANN as a graph for this, if you follow the connection arrows, can you reach `start_node` from `end_node`?
     pub fn would_form_cycle(&self, start_node: &Node, end_node: &Node) -> bool {
@@ -157,109 +193,19 @@ impl Genome {
         false
     }
 
-    /// Check if a node gene has more than one connection gene pointing to it.
+    /// Check if a node gene has more than one enabled connection gene pointing to it.
     pub fn has_alternative_input(&self, node: Id, exclude: Id) -> bool {
         self.connections()
-            .filter(|connection| connection.output == node)
+            .filter(|connection| connection.enabled && connection.output == node)
             .any(|connection| connection.input != exclude)
     }
 
-    /// Check if a node gene has more than one connection gene leaving it.
+    /// Check if a node gene has more than one enabled connection gene leaving it.
     pub fn has_alternative_output(&self, node: Id, exclude: Id) -> bool {
         self.connections()
-            .filter(|connection| connection.input == node)
+            .filter(|connection| connection.enabled && connection.input == node)
             .any(|connection| connection.output != exclude)
     }
-
-    /// Get the encoded neural network as a string in [DOT][1] format.
-    ///
-    /// The output can be [visualized here][2] for example.
-    ///
-    /// [1]: https://www.graphviz.org/doc/info/lang.html
-    /// [2]: https://dreampuf.github.io/GraphvizOnline
-    pub fn dot(genome: &Self) -> String {
-        let mut dot = "digraph {\n".to_owned();
-        dot.push_str("\tgraph [splines=curved ranksep=8]\n");
-
-        dot.push_str("\tsubgraph cluster_inputs {\n");
-        dot.push_str("\t\tgraph [label=\"Inputs\"]\n");
-        dot.push_str("\t\tnode [color=\"#D6B656\", fillcolor=\"#FFF2CC\", style=\"filled\"]\n");
-        dot.push_str("\n");
-        for node in genome.inputs.iter() {
-            // fill color: FFF2CC
-            // line color: D6B656
-
-            dot.push_str(&format!(
-                "\t\t{} [label={:?}];\n",
-                node.id.0, node.activation
-            ));
-        }
-        dot.push_str("\t}\n");
-
-        dot.push_str("\tsubgraph hidden {\n");
-        dot.push_str("\t\tgraph [label=\"Hidden\" rank=\"same\"]\n");
-        dot.push_str("\t\tnode [color=\"#6C8EBF\", fillcolor=\"#DAE8FC\", style=\"filled\"]\n");
-        dot.push_str("\n");
-        for node in genome.hidden.iter() {
-            // fill color: DAE8FC
-            // line color: 6C8EBF
-
-            dot.push_str(&format!(
-                "\t\t{} [label={:?}];\n",
-                node.id.0, node.activation
-            ));
-        }
-        dot.push_str("\t}\n");
-
-        dot.push_str("\tsubgraph cluster_outputs {\n");
-        dot.push_str("\t\tgraph [label=\"Outputs\" labelloc=\"b\"]\n");
-        dot.push_str("\t\tnode [color=\"#9673A6\", fillcolor=\"#E1D5E7\", style=\"filled\"]\n");
-        dot.push_str("\n");
-        for node in genome.outputs.iter() {
-            // fill color: E1D5E7
-            // line color: 9673A6
-
-            dot.push_str(&format!(
-                "\t\t{} [label={:?}];\n",
-                node.id.0, node.activation
-            ));
-        }
-        dot.push_str("\t}\n");
-
-        dot.push_str("\n");
-
-        dot.push_str("\tsubgraph feedforward_connections {\n");
-        dot.push_str("\n");
-        for connection in genome.feed_forward.iter() {
-            dot.push_str(&format!(
-                "\t\t{0} -> {1} [label=\"\" arrowsize={3:?} penwidth={3:?} tooltip={2:?} labeltooltip={2:?}];\n",
-                connection.input.0,
-                connection.output.0,
-                connection.weight,
-                connection.weight.abs() * 0.95 + 0.05
-            ));
-        }
-        dot.push_str("\t}\n");
-
-        dot.push_str("\tsubgraph recurrent_connections {\n");
-        dot.push_str("\t\tedge [color=\"#FF8000\"]\n");
-        dot.push_str("\n");
-        for connection in genome.recurrent.iter() {
-            // color: FF8000
-
-            dot.push_str(&format!(
-                "\t\t{0} -> {1} [label=\"\" arrowsize={3:?} penwidth={3:?} tooltip={2:?} labeltooltip={2:?}];\n",
-                connection.input.0,
-                connection.output.0,
-                connection.weight,
-                connection.weight.abs() * 0.95 + 0.05
-            ));
-        }
-        dot.push_str("\t}\n");
-
-        dot.push_str("}\n");
-        dot
-    }
 }
 
 #[cfg(test)]
@@ -377,6 +323,42 @@ mod tests {
         assert!(!genome.has_alternative_output(Id(0), Id(1)))
     }
 
+    #[test]
+    fn ignore_disabled_alternatives() {
+        let genome = Genome {
+            inputs: Genes(vec![Node::input(Id(0), 0)].iter().cloned().collect()),
+            hidden: Genes(
+                vec![Node::hidden(Id(2), Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            outputs: Genes(
+                vec![Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                vec![
+                    Connection::new(Id(0), 1.0, Id(2)),
+                    Connection::new(Id(2), 1.0, Id(1)),
+                    Connection {
+                        enabled: false,
+                        ..Connection::new(Id(0), 1.0, Id(1))
+                    },
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            ..Default::default()
+        };
+
+        assert!(!genome.has_alternative_input(Id(1), Id(2)));
+        assert!(!genome.has_alternative_output(Id(0), Id(2)));
+    }
+
     #[test]
     fn crossover() {
         let parameters = Parameters::default();
@@ -387,11 +369,11 @@ mod tests {
         let rng = &mut thread_rng();
 
         // mutate genome_0
-        Mutations::add_node(&Activation::all(), &mut genome_0, rng);
+        Mutations::add_node(&Activation::all(), &mut genome_0, rng).unwrap();
 
         // mutate genome_1
-        Mutations::add_node(&Activation::all(), &mut genome_1, rng);
-        Mutations::add_node(&Activation::all(), &mut genome_1, rng);
+        Mutations::add_node(&Activation::all(), &mut genome_1, rng).unwrap();
+        Mutations::add_node(&Activation::all(), &mut genome_1, rng).unwrap();
 
         // shorter genome is fitter genome
         let offspring = genome_0.cross_in(&genome_1);
@@ -400,6 +382,80 @@ mod tests {
         assert_eq!(offspring.feed_forward.len(), 3);
     }
 
+    #[test]
+    fn crossover_inherits_disabled_connections() {
+        let parameters = Parameters::basic(1, 1);
+        let genome_0 = Genome::initialized(&parameters);
+        let mut genome_1 = genome_0.clone();
+
+        let mut connection = genome_1.feed_forward.iter().next().cloned().unwrap();
+        connection.enabled = false;
+        genome_1.feed_forward.replace(connection);
+
+        let rng = &mut thread_rng();
+
+        let disabled = (0..10000)
+            .filter(|_| {
+                !genome_0
+                    .cross_in_with_rng(&genome_1, rng)
+                    .feed_forward
+                    .iter()
+                    .next()
+                    .unwrap()
+                    .enabled
+            })
+            .count();
+
+        assert!((disabled as f64 / 10000.0 - Connection::INHERIT_DISABLED_CHANCE).abs() < 0.03);
+
+        // both parents enabled
+        assert!(genome_0
+            .cross_in(&genome_0)
+            .feed_forward
+            .iter()
+            .all(|connection| connection.enabled));
+    }
+
+    #[test]
+    fn init_connects_bias_to_all_outputs() {
+        let parameters = Parameters {
+            structure: Structure {
+                number_of_inputs: 3,
+                number_of_outputs: 2,
+                percent_of_connected_inputs: 0.0,
+                bias: true,
+                ..Default::default()
+            },
+            mutations: vec![],
+            ..Default::default()
+        };
+
+        let genome = Genome::initialized(&parameters);
+        let bias = genome.bias.as_ref().unwrap();
+
+        assert_eq!(genome.feed_forward.len(), 2);
+        assert!(genome
+            .feed_forward
+            .iter()
+            .all(|connection| connection.input == bias.id));
+        assert!(genome.contains(bias.id));
+        assert_eq!(genome.nodes().count(), 6);
+    }
+
+    #[test]
+    fn bias_does_not_change_io_ids() {
+        let mut parameters = Parameters::basic(3, 2);
+
+        let genome_without_bias = Genome::uninitialized(&parameters);
+        parameters.structure.bias = true;
+        let genome_with_bias = Genome::uninitialized(&parameters);
+
+        assert_eq!(genome_without_bias.inputs, genome_with_bias.inputs);
+        assert_eq!(genome_without_bias.outputs, genome_with_bias.outputs);
+        assert!(genome_without_bias.bias.is_none());
+        assert!(genome_with_bias.bias.is_some());
+    }
+
     #[test]
     fn detect_no_cycle() {
         let parameters = Parameters::default();
@@ -547,134 +603,4 @@ mod tests {
 
         assert_eq!(genome_0_hash, genome_1_hash);
     }
-
-    #[test]
-    fn create_dot_from_genome() {
-        let genome = Genome {
-            inputs: Genes(vec![Node::input(Id(0), 0)].iter().cloned().collect()),
-            outputs: Genes(
-                vec![Node::output(Id(1), 0, Activation::Linear)]
-                    .iter()
-                    .cloned()
-                    .collect(),
-            ),
-            hidden: Genes(
-                vec![Node::hidden(Id(2), Activation::Tanh)]
-                    .iter()
-                    .cloned()
-                    .collect(),
-            ),
-            feed_forward: Genes(
-                vec![
-                    Connection::new(Id(0), 0.25795942718883524, Id(2)),
-                    Connection::new(Id(2), -0.09736946507786626, Id(1)),
-                ]
-                .iter()
-                .cloned()
-                .collect(),
-            ),
-            recurrent: Genes(
-                vec![Connection::new(Id(1), 0.19777863112749228, Id(2))]
-                    .iter()
-                    .cloned()
-                    .collect(),
-            ),
-            ..Default::default()
-        };
-
-        // let dot = "digraph {\n\t0 [label=Linear color=\"#D6B656\" fillcolor=\"#FFF2CC\" style=\"filled\"];\n\t2 [label=Tanh color=\"#6C8EBF\" fillcolor=\"#DAE8FC\" style=\"filled\"];\n\t1 [label=Linear color=\"#9673A6\" fillcolor=\"#E1D5E7\" style=\"filled\"];\n\t0 -> 2 [label=0.25795942718883524];\n\t2 -> 1 [label=0.09736946507786626];\n\t1 -> 2 [label=0.19777863112749228 color=\"#FF8000\"];\n}\n";
-
-        let dot = "digraph {
-\tgraph [splines=curved ranksep=8]
-\tsubgraph cluster_inputs {
-\t\tgraph [label=\"Inputs\"]
-\t\tnode [color=\"#D6B656\", fillcolor=\"#FFF2CC\", style=\"filled\"]
-
-\t\t0 [label=Linear];
-\t}
-\tsubgraph hidden {
-\t\tgraph [label=\"Hidden\" rank=\"same\"]
-\t\tnode [color=\"#6C8EBF\", fillcolor=\"#DAE8FC\", style=\"filled\"]
-
-\t\t2 [label=Tanh];
-\t}
-\tsubgraph cluster_outputs {
-\t\tgraph [label=\"Outputs\" labelloc=\"b\"]
-\t\tnode [color=\"#9673A6\", fillcolor=\"#E1D5E7\", style=\"filled\"]
-
-\t\t1 [label=Linear];
-\t}
-
-\tsubgraph feedforward_connections {
-
-\t\t0 -> 2 [label=\"\" arrowsize=0.29506145582939347 penwidth=0.29506145582939347 tooltip=0.25795942718883524 labeltooltip=0.25795942718883524];
-\t\t2 -> 1 [label=\"\" arrowsize=0.14250099182397294 penwidth=0.14250099182397294 tooltip=-0.09736946507786626 labeltooltip=-0.09736946507786626];
-\t}
-\tsubgraph recurrent_connections {
-\t\tedge [color=\"#FF8000\"]
-
-\t\t1 -> 2 [label=\"\" arrowsize=0.23788969957111766 penwidth=0.23788969957111766 tooltip=0.19777863112749228 labeltooltip=0.19777863112749228];
-\t}
-}
-";
-        assert_eq!(&Genome::dot(&genome), dot)
-    }
-
-    #[test]
-    fn print_big_dot() {
-        let parameters = Parameters {
-            structure: Structure {
-                number_of_inputs: 10,
-                number_of_outputs: 10,
-                percent_of_connected_inputs: 0.2,
-                ..Default::default()
-            },
-            mutations: vec![
-                Mutations::ChangeWeights {
-                    chance: 1.0,
-                    percent_perturbed: 0.5,
-                    standard_deviation: 0.1,
-                },
-                Mutations::ChangeActivation {
-                    chance: 0.05,
-                    activation_pool: vec![
-                        Activation::Linear,
-                        Activation::Sigmoid,
-                        Activation::Tanh,
-                        Activation::Gaussian,
-                        Activation::Step,
-                        Activation::Sine,
-                        Activation::Cosine,
-                        Activation::Inverse,
-                        Activation::Absolute,
-                        Activation::Relu,
-                    ],
-                },
-                Mutations::AddNode {
-                    chance: 0.005,
-                    activation_pool: vec![
-                        Activation::Linear,
-                        Activation::Sigmoid,
-                        Activation::Tanh,
-                        Activation::Gaussian,
-                        Activation::Step,
-                        Activation::Sine,
-                        Activation::Cosine,
-                        Activation::Inverse,
-                        Activation::Absolute,
-                        Activation::Relu,
-                    ],
-                },
-                Mutations::AddConnection { chance: 0.01 },
-                Mutations::AddRecurrentConnection { chance: 0.01 },
-            ],
-        };
-        let mut genome = Genome::initialized(&parameters);
-
-        for _ in 0..1000 {
-            genome.mutate(&parameters);
-        }
-
-        print!("{}", Genome::dot(&genome));
-    }
 }
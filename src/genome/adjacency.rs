@@ -0,0 +1,482 @@
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+};
+
+use seahash::SeaHasher;
+use thiserror::Error;
+
+use crate::{
+    genes::{Activation, Connection, Id, Node},
+    parameters::Structure,
+    Genome, GenomeViolation,
+};
+
+/// A square matrix in compressed sparse row (CSR) format.
+///
+/// Row `i` lists the incoming weights of node `i`, i.e. `columns[row_offsets[i]..row_offsets[i + 1]]` are the source nodes and `values` the corresponding weights.
+/// Multiplying the matrix with the vector of node outputs therefore yields the weighted input sum of every node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMatrix {
+    pub size: usize,
+    pub row_offsets: Vec<usize>,
+    pub columns: Vec<usize>,
+    pub values: Vec<f64>,
+}
+
+impl Default for SparseMatrix {
+    fn default() -> Self {
+        Self::from_entries(0, Vec::new())
+    }
+}
+
+impl SparseMatrix {
+    /// Builds the matrix from `(row, column, value)` entries in any order.
+    pub fn from_entries(size: usize, mut entries: Vec<(usize, usize, f64)>) -> Self {
+        entries.sort_unstable_by_key(|&(row, column, _)| (row, column));
+
+        let mut row_offsets = vec![0; size + 1];
+        for &(row, _, _) in &entries {
+            row_offsets[row + 1] += 1;
+        }
+        for row in 0..size {
+            row_offsets[row + 1] += row_offsets[row];
+        }
+
+        Self {
+            size,
+            row_offsets,
+            columns: entries.iter().map(|&(_, column, _)| column).collect(),
+            values: entries.iter().map(|&(_, _, value)| value).collect(),
+        }
+    }
+
+    /// Builds the matrix from rows of a dense square matrix, zero entries are left out.
+    pub fn from_dense(dense: &[Vec<f64>]) -> Self {
+        let entries = dense
+            .iter()
+            .enumerate()
+            .flat_map(|(row, values)| {
+                values
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &value)| value != 0.0)
+                    .map(move |(column, &value)| (row, column, value))
+            })
+            .collect();
+        Self::from_entries(dense.len(), entries)
+    }
+
+    pub fn to_dense(&self) -> Vec<Vec<f64>> {
+        let mut dense = vec![vec![0.0; self.size]; self.size];
+        for (row, column, value) in self.entries() {
+            dense[row][column] = value;
+        }
+        dense
+    }
+
+    /// Iterates all stored `(row, column, value)` entries.
+    pub fn entries(&self) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+        self.row_offsets
+            .windows(2)
+            .enumerate()
+            .flat_map(move |(row, offsets)| {
+                (offsets[0]..offsets[1])
+                    .map(move |entry| (row, self.columns[entry], self.values[entry]))
+            })
+    }
+
+    fn is_well_formed(&self) -> bool {
+        self.row_offsets.len() == self.size + 1
+            && self.row_offsets.first() == Some(&0)
+            && self
+                .row_offsets
+                .windows(2)
+                .all(|offsets| offsets[0] <= offsets[1])
+            && self.row_offsets.last() == Some(&self.columns.len())
+            && self.columns.len() == self.values.len()
+            && self.columns.iter().all(|&column| column < self.size)
+    }
+}
+
+/// Matrix representation of a [`Genome`], see [`Genome::to_adjacency`].
+///
+/// Nodes are indexed as inputs by order, the bias node if present, hidden nodes in topological order and outputs by order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Adjacency {
+    /// Id of the node at every index.
+    pub nodes: Vec<Id>,
+    pub number_of_inputs: usize,
+    pub bias: bool,
+    pub number_of_outputs: usize,
+    pub feed_forward: SparseMatrix,
+    pub recurrent: SparseMatrix,
+    pub activations: Vec<Activation>,
+    pub biases: Vec<f64>,
+    pub responses: Vec<f64>,
+}
+
+impl Adjacency {
+    pub fn index_of(&self, id: Id) -> Option<usize> {
+        self.nodes.iter().position(|&node| node == id)
+    }
+}
+
+/// Reasons [`Genome::to_adjacency`] and [`Genome::from_adjacency`] can fail.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum AdjacencyError {
+    #[error("Expected {expected} entries in {field} but found {found}.")]
+    DimensionMismatch {
+        field: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    #[error("There are more input, bias and output nodes than nodes.")]
+    TooFewNodes,
+    #[error("The {0} matrix is not in valid CSR format.")]
+    MalformedMatrix(&'static str),
+    #[error("The described network is not a valid genome: {0:?}")]
+    InvalidGenome(Vec<GenomeViolation>),
+    #[error("A connection references the node {0:?} which is not part of the genome.")]
+    UnknownNode(Id),
+}
+
+impl Genome {
+    /// Exports the genome as weight matrices for use in linear algebra code.
+    ///
+    /// Feed-forward and recurrent connections are separate matrices, disabled connections are left out.
+    /// Fails with [`AdjacencyError::UnknownNode`] when a connection references a node that is not part of the genome.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use set_genome::{Genome, Parameters};
+    ///
+    /// let genome = Genome::initialized(&Parameters::basic(2, 1));
+    ///
+    /// let adjacency = genome.to_adjacency().unwrap();
+    /// let weights = adjacency.feed_forward.to_dense();
+    ///
+    /// // the output at index 2 receives both inputs
+    /// assert!(weights[2][0] != 0.0 && weights[2][1] != 0.0);
+    /// ```
+    pub fn to_adjacency(&self) -> Result<Adjacency, AdjacencyError> {
+        let depths = self.feed_forward_depths();
+        let mut hidden = self.hidden.iter().collect::<Vec<_>>();
+        hidden.sort_unstable_by_key(|node| (depths.get(&node.id).copied(), node.id));
+
+        let nodes = self
+            .inputs
+            .as_ordered_vec()
+            .into_iter()
+            .chain(self.bias.iter())
+            .chain(hidden)
+            .chain(self.outputs.as_ordered_vec())
+            .collect::<Vec<_>>();
+        let index = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.id, index))
+            .collect::<HashMap<_, _>>();
+
+        let lookup = |id: Id| {
+            index
+                .get(&id)
+                .copied()
+                .ok_or(AdjacencyError::UnknownNode(id))
+        };
+
+        let matrix = |connections: &mut dyn Iterator<Item = &Connection>| {
+            Ok(SparseMatrix::from_entries(
+                nodes.len(),
+                connections
+                    .filter(|connection| connection.enabled)
+                    .map(|connection| {
+                        Ok((
+                            lookup(connection.output)?,
+                            lookup(connection.input)?,
+                            connection.weight,
+                        ))
+                    })
+                    .collect::<Result<_, AdjacencyError>>()?,
+            ))
+        };
+
+        Ok(Adjacency {
+            number_of_inputs: self.inputs.len(),
+            bias: self.bias.is_some(),
+            number_of_outputs: self.outputs.len(),
+            feed_forward: matrix(&mut self.feed_forward.iter())?,
+            recurrent: matrix(&mut self.recurrent.iter())?,
+            activations: nodes.iter().map(|node| node.activation).collect(),
+            biases: nodes.iter().map(|node| node.bias).collect(),
+            responses: nodes.iter().map(|node| node.response).collect(),
+            nodes: nodes.iter().map(|node| node.id).collect(),
+        })
+    }
+
+    /// Creates a genome from its matrix representation, e.g. to import a hand-designed network.
+    ///
+    /// `nodes` of the adjacency are ignored: input, bias and output nodes get the ids [`Genome::new`] assigns for the same number of inputs and outputs and the default seed,
+    /// hidden nodes get fresh ids.
+    /// Activation, bias and response of input and bias nodes are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use set_genome::{activations::Activation, Adjacency, Genome, SparseMatrix};
+    ///
+    /// // one input feeding one output through one hidden node
+    /// let adjacency = Adjacency {
+    ///     number_of_inputs: 1,
+    ///     number_of_outputs: 1,
+    ///     feed_forward: SparseMatrix::from_dense(&[
+    ///         vec![0.0, 0.0, 0.0],
+    ///         vec![0.5, 0.0, 0.0],
+    ///         vec![0.0, 2.0, 0.0],
+    ///     ]),
+    ///     recurrent: SparseMatrix::from_dense(&vec![vec![0.0; 3]; 3]),
+    ///     activations: vec![Activation::Linear, Activation::Relu, Activation::Tanh],
+    ///     biases: vec![0.0; 3],
+    ///     responses: vec![1.0; 3],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let genome = Genome::from_adjacency(&adjacency).unwrap();
+    ///
+    /// assert_eq!(genome.hidden.len(), 1);
+    /// assert_eq!(genome.feed_forward.len(), 2);
+    /// ```
+    pub fn from_adjacency(adjacency: &Adjacency) -> Result<Genome, AdjacencyError> {
+        let size = adjacency.activations.len();
+        for (field, found) in [
+            ("biases", adjacency.biases.len()),
+            ("responses", adjacency.responses.len()),
+            ("feed_forward", adjacency.feed_forward.size),
+            ("recurrent", adjacency.recurrent.size),
+        ] {
+            if found != size {
+                return Err(AdjacencyError::DimensionMismatch {
+                    field,
+                    expected: size,
+                    found,
+                });
+            }
+        }
+        if !adjacency.feed_forward.is_well_formed() {
+            return Err(AdjacencyError::MalformedMatrix("feed_forward"));
+        }
+        if !adjacency.recurrent.is_well_formed() {
+            return Err(AdjacencyError::MalformedMatrix("recurrent"));
+        }
+
+        let number_of_fixed =
+            adjacency.number_of_inputs + adjacency.bias as usize + adjacency.number_of_outputs;
+        if number_of_fixed > size {
+            return Err(AdjacencyError::TooFewNodes);
+        }
+
+        let mut genome = Genome::new(&Structure {
+            number_of_inputs: adjacency.number_of_inputs,
+            number_of_outputs: adjacency.number_of_outputs,
+            bias: adjacency.bias,
+            ..Default::default()
+        });
+
+        let mut ids = genome
+            .inputs
+            .as_ordered_vec()
+            .into_iter()
+            .chain(genome.bias.iter())
+            .map(|node| node.id)
+            .collect::<Vec<_>>();
+        let outputs = genome
+            .outputs
+            .as_ordered_vec()
+            .into_iter()
+            .map(|node| node.id)
+            .collect::<Vec<_>>();
+
+        let parameters = |node: Node, index: usize| Node {
+            activation: adjacency.activations[index],
+            bias: adjacency.biases[index],
+            response: adjacency.responses[index],
+            ..node
+        };
+
+        // consecutive hidden ids keep nodes of equal depth in their given order on export
+        let mut id_hasher = SeaHasher::new();
+        ids.hash(&mut id_hasher);
+        outputs.hash(&mut id_hasher);
+        let mut next_id = id_hasher.finish();
+
+        for index in ids.len()..size - adjacency.number_of_outputs {
+            while genome.contains(Id(next_id)) {
+                next_id = next_id.wrapping_add(1);
+            }
+            genome.hidden.insert(parameters(
+                Node::hidden(Id(next_id), Activation::Linear),
+                index,
+            ));
+            ids.push(Id(next_id));
+            next_id = next_id.wrapping_add(1);
+        }
+
+        let first_output = ids.len();
+        genome.outputs = genome
+            .outputs
+            .drain()
+            .map(|node| parameters(node.clone(), first_output + node.order))
+            .collect();
+        ids.extend(outputs);
+
+        for (row, column, weight) in adjacency.feed_forward.entries() {
+            genome
+                .feed_forward
+                .insert(Connection::new(ids[column], weight, ids[row]));
+        }
+        for (row, column, weight) in adjacency.recurrent.entries() {
+            genome
+                .recurrent
+                .insert(Connection::new(ids[column], weight, ids[row]));
+        }
+
+        genome.validate().map_err(AdjacencyError::InvalidGenome)?;
+        genome.order_hidden_nodes();
+        Ok(genome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use super::{Adjacency, AdjacencyError, SparseMatrix};
+    use crate::{
+        activations::Activation, genes::Connection, Genome, GenomeViolation, Id, Parameters,
+    };
+
+    #[test]
+    fn sparse_matrix_round_trip() {
+        let dense = vec![
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 0.0],
+            vec![2.0, 0.0, 3.0],
+        ];
+
+        let sparse = SparseMatrix::from_dense(&dense);
+
+        assert_eq!(sparse.row_offsets, vec![0, 1, 1, 3]);
+        assert_eq!(sparse.columns, vec![1, 0, 2]);
+        assert_eq!(sparse.to_dense(), dense);
+    }
+
+    #[test]
+    fn adjacency_round_trip() {
+        let mut parameters = Parameters::default();
+        parameters.structure.number_of_inputs = 3;
+        parameters.structure.number_of_outputs = 2;
+        parameters.structure.bias = true;
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        let mut genome = Genome::initialized_with_rng(&parameters, &mut rng);
+        for _ in 0..200 {
+            let _ = genome.mutate_with_rng(&parameters, &mut rng);
+        }
+        // disabled connections are not exported
+        genome.feed_forward.retain(|connection| connection.enabled);
+        genome.recurrent.retain(|connection| connection.enabled);
+
+        let adjacency = genome.to_adjacency().unwrap();
+        let imported = Genome::from_adjacency(&adjacency).unwrap();
+
+        assert_eq!(imported.inputs, genome.inputs);
+        assert_eq!(imported.bias, genome.bias);
+        assert_eq!(imported.outputs, genome.outputs);
+        assert_eq!(imported.hidden.len(), genome.hidden.len());
+
+        let reexported = imported.to_adjacency().unwrap();
+        assert_eq!(reexported.feed_forward, adjacency.feed_forward);
+        assert_eq!(reexported.recurrent, adjacency.recurrent);
+        assert_eq!(reexported.activations, adjacency.activations);
+    }
+
+    #[test]
+    fn hidden_nodes_are_indexed_topologically() {
+        let adjacency = Adjacency {
+            number_of_inputs: 1,
+            number_of_outputs: 1,
+            feed_forward: SparseMatrix::from_dense(&[
+                vec![0.0, 0.0, 0.0, 0.0],
+                vec![0.0, 0.0, 1.0, 0.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0, 0.0],
+            ]),
+            recurrent: SparseMatrix::from_dense(&vec![vec![0.0; 4]; 4]),
+            activations: vec![Activation::Linear; 4],
+            biases: vec![0.0; 4],
+            responses: vec![1.0; 4],
+            ..Default::default()
+        };
+
+        let genome = Genome::from_adjacency(&adjacency).unwrap();
+
+        // node 2 feeds node 1, so their indices swap on export
+        assert_eq!(
+            genome.to_adjacency().unwrap().feed_forward.to_dense(),
+            vec![
+                vec![0.0, 0.0, 0.0, 0.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0, 0.0],
+                vec![0.0, 0.0, 1.0, 0.0],
+            ]
+        );
+    }
+
+    #[test]
+    fn reject_invalid_adjacency() {
+        let adjacency = Adjacency {
+            number_of_inputs: 1,
+            number_of_outputs: 1,
+            feed_forward: SparseMatrix::from_dense(&[vec![0.0, 1.0], vec![1.0, 0.0]]),
+            recurrent: SparseMatrix::from_dense(&vec![vec![0.0; 2]; 2]),
+            activations: vec![Activation::Linear; 2],
+            biases: vec![0.0; 2],
+            responses: vec![1.0; 1],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Genome::from_adjacency(&adjacency),
+            Err(AdjacencyError::DimensionMismatch {
+                field: "responses",
+                expected: 2,
+                found: 1
+            })
+        );
+
+        let adjacency = Adjacency {
+            responses: vec![1.0; 2],
+            ..adjacency
+        };
+        let violations = match Genome::from_adjacency(&adjacency) {
+            Err(AdjacencyError::InvalidGenome(violations)) => violations,
+            other => panic!("unexpected result {:?}", other),
+        };
+        assert!(violations.contains(&GenomeViolation::FeedForwardCycle));
+    }
+
+    #[test]
+    fn reject_unknown_node() {
+        let mut genome = Genome::initialized(&Parameters::basic(1, 1));
+        let output = genome.outputs.iter().next().unwrap().id;
+        genome
+            .recurrent
+            .insert(Connection::new(Id(42), 1.0, output));
+
+        assert_eq!(
+            genome.to_adjacency(),
+            Err(AdjacencyError::UnknownNode(Id(42)))
+        );
+    }
+}
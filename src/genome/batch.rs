@@ -0,0 +1,219 @@
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use rayon::prelude::*;
+
+use crate::{CompatibilityDistance, CrossoverStrategy, Genome, MutationResult, Parameters};
+
+/// One seed per item, drawn in order from `seed` so results do not depend on the number of threads.
+fn seeds(seed: u64, amount: usize) -> Vec<u64> {
+    let mut rng = SmallRng::seed_from_u64(seed);
+    (0..amount).map(|_| rng.gen()).collect()
+}
+
+impl Genome {
+    /// Mutates all `genomes` in parallel like [`Genome::mutate_with_rng`].
+    ///
+    /// Every genome draws from its own rng, seeded by its position and `seed`, so the outcome is reproducible regardless of scheduling.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use set_genome::{Genome, Parameters};
+    ///
+    /// let parameters = Parameters::default();
+    /// let mut genomes = vec![Genome::initialized(&parameters); 100];
+    ///
+    /// let results = Genome::mutate_batch(&mut genomes, &parameters, 42);
+    ///
+    /// assert_eq!(results.len(), 100);
+    /// ```
+    pub fn mutate_batch(
+        genomes: &mut [Genome],
+        parameters: &Parameters,
+        seed: u64,
+    ) -> Vec<MutationResult> {
+        let seeds = seeds(seed, genomes.len());
+
+        genomes
+            .par_iter_mut()
+            .zip(seeds)
+            .map(|(genome, seed)| {
+                genome.mutate_with_rng(parameters, &mut SmallRng::seed_from_u64(seed))
+            })
+            .collect()
+    }
+
+    /// Produces one offspring per pair of indices into `parents` in parallel, by crossing both parents according to `strategy` and mutating the result.
+    ///
+    /// The first parent of each pair is considered the fitter one, see [`Genome::cross_in_with_strategy`].
+    /// Failed mutations are ignored like in `Population::reproduce`, and the outcome is reproducible from `seed` like in [`Genome::mutate_batch`].
+    pub fn offspring_batch(
+        parents: &[Genome],
+        pairs: &[(usize, usize)],
+        strategy: &CrossoverStrategy,
+        parameters: &Parameters,
+        seed: u64,
+    ) -> Vec<Genome> {
+        let seeds = seeds(seed, pairs.len());
+
+        pairs
+            .par_iter()
+            .zip(seeds)
+            .map(|(&(fitter, other), seed)| {
+                let rng = &mut SmallRng::seed_from_u64(seed);
+                let mut offspring =
+                    parents[fitter].cross_in_with_strategy(&parents[other], strategy, rng);
+                let _ = offspring.mutate_with_rng(parameters, rng);
+                offspring
+            })
+            .collect()
+    }
+}
+
+impl CompatibilityDistance {
+    /// Computes the distance between all pairs of `genomes` in parallel.
+    ///
+    /// The result is symmetric with zeros on the diagonal, row `i` holds the distances of `genomes[i]` to all genomes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use set_genome::{CompatibilityDistance, Genome, Parameters};
+    ///
+    /// let parameters = Parameters::basic(10, 10);
+    /// let genomes = (0..10).map(|_| Genome::initialized(&parameters)).collect::<Vec<_>>();
+    ///
+    /// let matrix = CompatibilityDistance::from_parameters(&parameters).distance_matrix(&genomes);
+    ///
+    /// assert_eq!(matrix[3][7], matrix[7][3]);
+    /// assert_eq!(matrix[5][5], 0.0);
+    /// ```
+    pub fn distance_matrix(&self, genomes: &[Genome]) -> Vec<Vec<f64>> {
+        // only compute the upper triangle
+        let upper = (0..genomes.len())
+            .into_par_iter()
+            .map(|row| {
+                genomes[row + 1..]
+                    .iter()
+                    .map(|other| self.between(&genomes[row], other))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut matrix = vec![vec![0.0; genomes.len()]; genomes.len()];
+        for (row, distances) in upper.into_iter().enumerate() {
+            for (offset, distance) in distances.into_iter().enumerate() {
+                matrix[row][row + 1 + offset] = distance;
+                matrix[row + 1 + offset][row] = distance;
+            }
+        }
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use crate::{
+        activations::Activation, CompatibilityDistance, CrossoverStrategy, Genome, Parameters,
+    };
+
+    use super::seeds;
+
+    fn genomes(parameters: &Parameters) -> Vec<Genome> {
+        let rng = &mut SmallRng::seed_from_u64(7);
+        (0..50)
+            .map(|_| Genome::initialized_with_rng(parameters, rng))
+            .collect()
+    }
+
+    type Values = (Vec<(f64, bool)>, Vec<(Activation, f64, f64)>);
+
+    // genes compare equal by id only, so weights and node parameters are compared separately
+    fn values(genome: &Genome) -> Values {
+        (
+            genome
+                .feed_forward
+                .as_sorted_vec()
+                .into_iter()
+                .chain(genome.recurrent.as_sorted_vec())
+                .map(|connection| (connection.weight, connection.enabled))
+                .collect(),
+            genome
+                .hidden
+                .as_ordered_vec()
+                .into_iter()
+                .chain(genome.outputs.as_ordered_vec())
+                .map(|node| (node.activation, node.bias, node.response))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn mutate_batch_is_reproducible() {
+        let parameters = Parameters::default();
+
+        let mut parallel = genomes(&parameters);
+        for _ in 0..10 {
+            Genome::mutate_batch(&mut parallel, &parameters, 42);
+        }
+
+        let mut sequential = genomes(&parameters);
+        for _ in 0..10 {
+            for (genome, seed) in sequential.iter_mut().zip(seeds(42, 50)) {
+                let _ = genome.mutate_with_rng(&parameters, &mut SmallRng::seed_from_u64(seed));
+            }
+        }
+
+        assert_eq!(parallel, sequential);
+        assert_eq!(
+            parallel.iter().map(values).collect::<Vec<_>>(),
+            sequential.iter().map(values).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn offspring_batch_is_reproducible() {
+        let parameters = Parameters::default();
+        let mut parents = genomes(&parameters);
+        Genome::mutate_batch(&mut parents, &parameters, 1);
+
+        let pairs = (0..50).map(|i| (i, (i * 7) % 50)).collect::<Vec<_>>();
+        let offspring = |seed| {
+            Genome::offspring_batch(
+                &parents,
+                &pairs,
+                &CrossoverStrategy::default(),
+                &parameters,
+                seed,
+            )
+        };
+
+        assert_eq!(offspring(42).len(), 50);
+        assert_eq!(offspring(42), offspring(42));
+        assert_eq!(
+            offspring(42).iter().map(values).collect::<Vec<_>>(),
+            offspring(42).iter().map(values).collect::<Vec<_>>()
+        );
+        assert!(offspring(42).iter().all(|genome| genome.validate().is_ok()));
+    }
+
+    #[test]
+    fn distance_matrix_is_symmetric() {
+        let parameters = Parameters::basic(5, 3);
+        let genomes = genomes(&parameters);
+        let distance = CompatibilityDistance::from_parameters(&parameters);
+
+        let matrix = distance.distance_matrix(&genomes);
+
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(row[i], 0.0);
+            for (j, &value) in row.iter().enumerate() {
+                assert_eq!(value, matrix[j][i]);
+                if i < j {
+                    assert_eq!(value, distance.between(&genomes[i], &genomes[j]));
+                }
+            }
+        }
+    }
+}
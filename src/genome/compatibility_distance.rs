@@ -1,17 +1,91 @@
-use crate::Genome;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    genes::{Gene, Genes},
+    parameters::WeightBounds,
+    Genome, Parameters,
+};
+
+/// How the number of different genes is turned into a value between 0 and 1.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Normalization {
+    /// Different genes divided by all unique genes from both genomes.
+    #[default]
+    Percent,
+    /// Different genes divided by the number of genes in the larger genome, as proposed by NEAT.
+    /// Can exceed 1 when the genomes share few genes.
+    LargerGenome,
+}
+
+/// Weights of the aspects of the [`CompatibilityDistance`], usually read from [`Parameters::compatibility`].
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct CompatibilityFactors {
+    /// Factor of the different connections.
+    pub connections: f64,
+    /// Factor of the different recurrent connections, when missing they count as [`CompatibilityFactors::connections`].
+    pub recurrent_connections: Option<f64>,
+    /// Factor of the disjoint hidden nodes.
+    pub disjoint_nodes: f64,
+    /// Factor of the weight difference of matching connections.
+    pub weights: f64,
+    /// Factor of the different activations of matching hidden nodes.
+    pub activations: f64,
+    /// Factor of the bias and response difference of matching hidden and output nodes.
+    pub node_parameters: f64,
+    pub normalization: Normalization,
+}
+
+impl Default for CompatibilityFactors {
+    fn default() -> Self {
+        Self {
+            connections: 1.0,
+            recurrent_connections: None,
+            disjoint_nodes: 0.0,
+            weights: 0.5,
+            activations: 0.5,
+            node_parameters: 0.5,
+            normalization: Normalization::Percent,
+        }
+    }
+}
+
+/// The overall distance together with every aspect scaled by its factor, see [`CompatibilityDistance::breakdown`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub struct DistanceBreakdown {
+    pub overall: f64,
+    pub connections: f64,
+    pub recurrent_connections: f64,
+    pub disjoint_nodes: f64,
+    pub weights: f64,
+    pub activations: f64,
+    pub node_parameters: f64,
+}
 
 /// Mechanism to compute distances between genomes.
 ///
 /// Compatibility distance is a concept introduced in [NEAT] and defines a distance metric between genomes.
 /// It can be useful for other evolutionary mechanisms such as speciation.
 ///
-/// Three aspects amount to the resulting difference:
-/// - the amount of identical a.k.a shared connections between the genomes
+/// The following aspects amount to the resulting difference:
+/// - the amount of identical a.k.a shared connections between the genomes, including those originating from a bias node
+/// - optionally, the amount of shared recurrent connections, otherwise they count as regular connections, see [`CompatibilityDistance::with_recurrent_connections_factor`]
+/// - optionally, the amount of shared hidden nodes, see [`CompatibilityDistance::with_disjoint_nodes_factor`]
 /// - the total weight difference between shared connections
 /// - the number of different activations in identical nodes
+/// - optionally, the average difference in bias and response of identical hidden and output nodes, see [`CompatibilityDistance::with_node_parameters_factor`]
 ///
-/// Each aspect gives a normalized value between 0 and 1 and is then weighted by the corresponding factor.
-/// The computed difference is the normalized combination of the weighted aspects.
+/// Each aspect gives a non-negative value and is then weighted by the corresponding factor.
+/// Differing genes are normalized according to the [`Normalization`], see [`CompatibilityDistance::with_normalization`],
+/// which keeps them between 0 and 1 for [`Normalization::Percent`].
+/// Different activations are a percentage and thus always between 0 and 1.
+/// Weight differences are normalized by the range of the configured [`WeightBounds`], see [`CompatibilityDistance::with_weight_bounds`],
+/// and lie between 0 and 1 for bounded weights only, for unbounded weights the average weight difference is used as is.
+/// Node parameters are unbounded, their average difference is used as is and can exceed 1.
+/// The computed difference is the combination of the weighted aspects divided by the sum of all factors.
 ///
 /// For details read [here] part 2.5.1.
 ///
@@ -31,52 +105,80 @@ use crate::Genome;
 ///
 /// // Both calls are equivalent.
 /// assert!(distance.between(&genome_one, &genome_two) > 0.0);
-/// assert!(CompatibilityDistance::compatability_distance(&genome_one, &genome_two, 1.0, 1.0, 1.0).0 > 0.0);
+/// assert!(CompatibilityDistance::compatability_distance(&genome_one, &genome_two, 1.0, 1.0, 1.0).overall > 0.0);
 /// ```
 pub struct CompatibilityDistance {
-    factor_connections: f64,
-    factor_weights: f64,
-    factor_activations: f64,
+    factors: CompatibilityFactors,
+    maximum_weight_difference: Option<f64>,
 }
 
 impl CompatibilityDistance {
+    /// Assumes weights within the default bounds of \[-1, 1\] and ignores node parameters.
     pub fn with_factors(
         factor_connections: f64,
         factor_weights: f64,
         factor_activations: f64,
     ) -> Self {
+        Self::from_factors(&CompatibilityFactors {
+            connections: factor_connections,
+            weights: factor_weights,
+            activations: factor_activations,
+            node_parameters: 0.0,
+            ..Default::default()
+        })
+    }
+
+    /// Assumes weights within the default bounds of \[-1, 1\].
+    pub fn from_factors(factors: &CompatibilityFactors) -> Self {
+        Self {
+            factors: *factors,
+            maximum_weight_difference: WeightBounds::default().maximum_difference(),
+        }
+    }
+
+    /// Uses [`Parameters::compatibility`] and the configured weight bounds.
+    pub fn from_parameters(parameters: &Parameters) -> Self {
+        Self::from_factors(&parameters.compatibility).with_weight_bounds(&parameters.weights.bounds)
+    }
+
+    /// Includes the bias and response of nodes, see [`crate::Node`], weighted by `factor_node_parameters`.
+    pub fn with_node_parameters_factor(mut self, factor_node_parameters: f64) -> Self {
+        self.factors.node_parameters = factor_node_parameters;
+        self
+    }
+
+    /// Counts recurrent connections separately from feed-forward connections, weighted by `factor_recurrent_connections`.
+    pub fn with_recurrent_connections_factor(mut self, factor_recurrent_connections: f64) -> Self {
+        self.factors.recurrent_connections = Some(factor_recurrent_connections);
+        self
+    }
+
+    /// Includes hidden nodes present in only one of the genomes, weighted by `factor_disjoint_nodes`.
+    pub fn with_disjoint_nodes_factor(mut self, factor_disjoint_nodes: f64) -> Self {
+        self.factors.disjoint_nodes = factor_disjoint_nodes;
+        self
+    }
+
+    /// Changes how the number of different connections and nodes is normalized.
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.factors.normalization = normalization;
+        self
+    }
+
+    /// Normalizes weight differences according to the given bounds, usually [`crate::WeightConfig::bounds`].
+    pub fn with_weight_bounds(self, bounds: &WeightBounds) -> Self {
         Self {
-            factor_connections,
-            factor_weights,
-            factor_activations,
+            maximum_weight_difference: bounds.maximum_difference(),
+            ..self
         }
     }
 
     pub fn between(&self, genome_0: &Genome, genome_1: &Genome) -> f64 {
-        let Self {
-            factor_connections,
-            factor_weights,
-            factor_activations,
-        } = *self;
-
-        CompatibilityDistance::compatability_distance(
-            genome_0,
-            genome_1,
-            factor_connections,
-            factor_weights,
-            factor_activations,
-        )
-        .0
+        self.breakdown(genome_0, genome_1).overall
     }
 
     /// Directly compute the compatability distance.
     ///
-    /// The result is a 4-tuple of:
-    /// - the overall difference
-    /// - the scaled connection difference
-    /// - the scaled weight difference
-    /// - the scaled activation difference
-    ///
     /// # Example
     /// ```
     /// # use set_genome::{Genome, Parameters, CompatibilityDistance};
@@ -86,11 +188,12 @@ impl CompatibilityDistance {
     /// let genome_one = Genome::initialized(&parameters);
     /// let genome_two = Genome::initialized(&parameters);
     ///
-    /// assert!(CompatibilityDistance::compatability_distance(&genome_one, &genome_two, 1.0, 1.0, 1.0).0 > 0.0);
+    /// assert!(CompatibilityDistance::compatability_distance(&genome_one, &genome_two, 1.0, 1.0, 1.0).overall > 0.0);
     ///
-    /// assert_eq!(CompatibilityDistance::compatability_distance(&genome_one, &genome_one, 1.0, 1.0, 1.0).1, 0.0);
-    /// assert_eq!(CompatibilityDistance::compatability_distance(&genome_one, &genome_one, 1.0, 1.0, 1.0).2, 0.0);
-    /// assert_eq!(CompatibilityDistance::compatability_distance(&genome_one, &genome_one, 1.0, 1.0, 1.0).3, 0.0);
+    /// let same = CompatibilityDistance::compatability_distance(&genome_one, &genome_one, 1.0, 1.0, 1.0);
+    /// assert_eq!(same.connections, 0.0);
+    /// assert_eq!(same.weights, 0.0);
+    /// assert_eq!(same.activations, 0.0);
     /// ```
     pub fn compatability_distance(
         genome_0: &Genome,
@@ -98,9 +201,26 @@ impl CompatibilityDistance {
         factor_connections: f64,
         factor_weights: f64,
         factor_activations: f64,
-    ) -> (f64, f64, f64, f64) {
+    ) -> DistanceBreakdown {
+        Self::with_factors(factor_connections, factor_weights, factor_activations)
+            .breakdown(genome_0, genome_1)
+    }
+
+    /// Computes the overall distance and every scaled aspect.
+    pub fn breakdown(&self, genome_0: &Genome, genome_1: &Genome) -> DistanceBreakdown {
+        let CompatibilityFactors {
+            connections: factor_connections,
+            recurrent_connections: factor_recurrent_connections,
+            disjoint_nodes: factor_disjoint_nodes,
+            weights: factor_weights,
+            activations: factor_activations,
+            node_parameters: factor_node_parameters,
+            normalization,
+        } = self.factors;
+
         let mut weight_difference = 0.0;
         let mut activation_difference = 0.0;
+        let mut node_parameter_difference = 0.0;
 
         let matching_connections_count = (genome_0
             .feed_forward
@@ -117,15 +237,6 @@ impl CompatibilityDistance {
                 })
                 .count()) as f64;
 
-        let different_connections_count = (genome_0
-            .feed_forward
-            .iterate_unique_genes(&genome_1.feed_forward)
-            .count()
-            + genome_0
-                .recurrent
-                .iterate_unique_genes(&genome_1.recurrent)
-                .count()) as f64;
-
         let matching_nodes_count = genome_0
             .hidden
             .iterate_matching_genes(&genome_1.hidden)
@@ -136,12 +247,43 @@ impl CompatibilityDistance {
             })
             .count() as f64;
 
-        // Connection weights are capped between 1.0 and -1.0. So the maximum difference per matching connection is 2.0.
-        let maximum_weight_difference = matching_connections_count * 2.0;
+        let parameterized_nodes_count = genome_0
+            .hidden
+            .iterate_matching_genes(&genome_1.hidden)
+            .chain(genome_0.outputs.iterate_matching_genes(&genome_1.outputs))
+            .inspect(|(node_0, node_1)| {
+                node_parameter_difference +=
+                    (node_0.bias - node_1.bias).abs() + (node_0.response - node_1.response).abs();
+            })
+            .count() as f64;
 
-        // percent of different genes, considering all unique genes from both genomes
-        let scaled_connection_difference = factor_connections * different_connections_count
-            / (matching_connections_count + different_connections_count);
+        // Connection weights are kept within the configured bounds, without bounds the plain average difference is used.
+        let maximum_weight_difference =
+            matching_connections_count * self.maximum_weight_difference.unwrap_or(1.0);
+
+        // different genes according to the normalization, recurrent connections count as regular ones without their own factor
+        let (scaled_connection_difference, scaled_recurrent_connection_difference) =
+            match factor_recurrent_connections {
+                Some(factor_recurrent_connections) => (
+                    factor_connections
+                        * normalization
+                            .different_genes(&[(&genome_0.feed_forward, &genome_1.feed_forward)]),
+                    factor_recurrent_connections
+                        * normalization
+                            .different_genes(&[(&genome_0.recurrent, &genome_1.recurrent)]),
+                ),
+                None => (
+                    factor_connections
+                        * normalization.different_genes(&[
+                            (&genome_0.feed_forward, &genome_1.feed_forward),
+                            (&genome_0.recurrent, &genome_1.recurrent),
+                        ]),
+                    0.0,
+                ),
+            };
+
+        let scaled_disjoint_node_difference = factor_disjoint_nodes
+            * normalization.different_genes(&[(&genome_0.hidden, &genome_1.hidden)]);
 
         // average weight differences , considering matching connection genes
         let scaled_weight_difference = factor_weights
@@ -159,17 +301,58 @@ impl CompatibilityDistance {
                 0.0
             };
 
+        // average bias and response difference, considering matching hidden and output nodes
+        let scaled_node_parameter_difference = factor_node_parameters
+            * if parameterized_nodes_count > 0.0 {
+                node_parameter_difference / parameterized_nodes_count
+            } else {
+                0.0
+            };
+
         let overall_scaled_distance = (scaled_connection_difference
+            + scaled_recurrent_connection_difference
+            + scaled_disjoint_node_difference
             + scaled_weight_difference
-            + scaled_activation_difference)
-            / (factor_connections + factor_weights + factor_activations);
-
-        (
-            overall_scaled_distance,
-            scaled_connection_difference,
-            scaled_weight_difference,
-            scaled_activation_difference,
-        )
+            + scaled_activation_difference
+            + scaled_node_parameter_difference)
+            / (factor_connections
+                + factor_recurrent_connections.unwrap_or(0.0)
+                + factor_disjoint_nodes
+                + factor_weights
+                + factor_activations
+                + factor_node_parameters);
+
+        DistanceBreakdown {
+            overall: overall_scaled_distance,
+            connections: scaled_connection_difference,
+            recurrent_connections: scaled_recurrent_connection_difference,
+            disjoint_nodes: scaled_disjoint_node_difference,
+            weights: scaled_weight_difference,
+            activations: scaled_activation_difference,
+            node_parameters: scaled_node_parameter_difference,
+        }
+    }
+}
+
+impl Normalization {
+    /// Normalized amount of genes present in only one genome, summed over all given pairs of gene sets.
+    fn different_genes<T: Gene>(&self, pairs: &[(&Genes<T>, &Genes<T>)]) -> f64 {
+        let mut different = 0;
+        let mut total = 0;
+
+        for (genes_0, genes_1) in pairs {
+            different += genes_0.iterate_unique_genes(genes_1).count();
+            total += match self {
+                Normalization::Percent => genes_0.union(genes_1).count(),
+                Normalization::LargerGenome => genes_0.len().max(genes_1.len()),
+            };
+        }
+
+        if total > 0 {
+            different as f64 / total as f64
+        } else {
+            0.0
+        }
     }
 }
 
@@ -178,6 +361,7 @@ mod tests {
     use crate::{
         activations::Activation, genes::Genes,
         genome::compatibility_distance::CompatibilityDistance, Connection, Genome, Id, Node,
+        Normalization, WeightBounds,
     };
 
     #[test]
@@ -203,7 +387,8 @@ mod tests {
         let genome_1 = genome_0.clone();
 
         let delta =
-            CompatibilityDistance::compatability_distance(&genome_0, &genome_1, 1.0, 0.4, 0.0).0;
+            CompatibilityDistance::compatability_distance(&genome_0, &genome_1, 1.0, 0.4, 0.0)
+                .overall;
 
         assert!(delta.abs() < f64::EPSILON);
     }
@@ -243,12 +428,54 @@ mod tests {
             factor_weight,
             0.0,
         )
-        .0;
+        .overall;
 
         // factor 2 times 2 expressed difference over 2 possible difference over factor 2
         assert!((delta - factor_weight * 1.0 / 2.0 / factor_weight).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn compatability_distance_follows_weight_bounds() {
+        let genome_0 = Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            outputs: Genes(
+                [Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [Connection::new(Id(0), 0.0, Id(1))]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+
+        let mut genome_1 = genome_0.clone();
+
+        genome_1
+            .feed_forward
+            .replace(Connection::new(Id(0), 2.0, Id(1)));
+
+        let bounded = CompatibilityDistance::with_factors(0.0, 1.0, 0.0).with_weight_bounds(
+            &WeightBounds::Bounded {
+                min: -4.0,
+                max: 4.0,
+            },
+        );
+
+        // 2 expressed difference over 8 possible difference
+        assert!((bounded.between(&genome_0, &genome_1) - 2.0 / 8.0).abs() < f64::EPSILON);
+
+        let unbounded = CompatibilityDistance::with_factors(0.0, 1.0, 0.0)
+            .with_weight_bounds(&WeightBounds::Unbounded);
+
+        // average difference
+        assert!((unbounded.between(&genome_0, &genome_1) - 2.0).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn compatability_distance_different_connection_genome() {
         let genome_0 = Genome {
@@ -279,9 +506,185 @@ mod tests {
             .insert(Connection::new(Id(2), 2.0, Id(1)));
 
         let delta =
-            CompatibilityDistance::compatability_distance(&genome_0, &genome_1, 2.0, 0.0, 0.0).0;
+            CompatibilityDistance::compatability_distance(&genome_0, &genome_1, 2.0, 0.0, 0.0)
+                .overall;
 
         // factor 2 times 2 different genes over 3 total genes over factor 2
         assert!((delta - 2.0 * 2.0 / 3.0 / 2.0).abs() < f64::EPSILON);
     }
+
+    #[test]
+    fn compatability_distance_different_bias_connection_genome() {
+        let genome_0 = Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            bias: Some(Node::bias(Id(2))),
+            outputs: Genes(
+                [Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [Connection::new(Id(0), 1.0, Id(1))]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+
+        let mut genome_1 = genome_0.clone();
+
+        genome_1
+            .feed_forward
+            .insert(Connection::new(Id(2), 0.5, Id(1)));
+
+        let delta =
+            CompatibilityDistance::compatability_distance(&genome_0, &genome_1, 1.0, 0.0, 0.0)
+                .overall;
+
+        // one different gene over two total genes
+        assert!((delta - 1.0 / 2.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn compatability_distance_different_node_parameters() {
+        let genome_0 = Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            outputs: Genes(
+                [Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [Connection::new(Id(0), 1.0, Id(1))]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+
+        let mut genome_1 = genome_0.clone();
+        genome_1.outputs.replace(Node {
+            bias: 0.5,
+            response: 1.5,
+            ..Node::output(Id(1), 0, Activation::Linear)
+        });
+
+        let distance = CompatibilityDistance::with_factors(1.0, 1.0, 1.0);
+
+        assert!(distance.between(&genome_0, &genome_1).abs() < f64::EPSILON);
+        // (0.5 + 0.5) * 1.0 / (1.0 + 1.0 + 1.0 + 1.0)
+        assert!(
+            (distance
+                .with_node_parameters_factor(1.0)
+                .between(&genome_0, &genome_1)
+                - 0.25)
+                .abs()
+                < f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn compatability_distance_separate_recurrent_connections_and_nodes() {
+        let genome_0 = Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            outputs: Genes(
+                [Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [Connection::new(Id(0), 1.0, Id(1))]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+
+        let mut genome_1 = genome_0.clone();
+        genome_1
+            .hidden
+            .insert(Node::hidden(Id(2), Activation::Linear));
+        genome_1
+            .feed_forward
+            .insert(Connection::new(Id(0), 1.0, Id(2)));
+        genome_1
+            .feed_forward
+            .insert(Connection::new(Id(2), 1.0, Id(1)));
+        genome_1
+            .recurrent
+            .insert(Connection::new(Id(1), 1.0, Id(2)));
+
+        // three different connections over four total connections
+        let combined = CompatibilityDistance::with_factors(1.0, 0.0, 0.0);
+        assert!((combined.between(&genome_0, &genome_1) - 3.0 / 4.0).abs() < f64::EPSILON);
+
+        let separate = CompatibilityDistance::with_factors(1.0, 0.0, 0.0)
+            .with_recurrent_connections_factor(1.0)
+            .with_disjoint_nodes_factor(1.0)
+            .breakdown(&genome_0, &genome_1);
+
+        assert!((separate.connections - 2.0 / 3.0).abs() < f64::EPSILON);
+        assert!((separate.recurrent_connections - 1.0).abs() < f64::EPSILON);
+        assert!((separate.disjoint_nodes - 1.0).abs() < f64::EPSILON);
+        assert!((separate.overall - (2.0 / 3.0 + 1.0 + 1.0) / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn compatability_distance_normalized_by_larger_genome() {
+        let genome_0 = Genome {
+            inputs: Genes(
+                [Node::input(Id(0), 0), Node::input(Id(1), 1)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            outputs: Genes(
+                [Node::output(Id(2), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [Connection::new(Id(0), 1.0, Id(2))]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+
+        let mut genome_1 = genome_0.clone();
+        genome_1
+            .feed_forward
+            .insert(Connection::new(Id(1), 1.0, Id(2)));
+
+        let distance = CompatibilityDistance::with_factors(1.0, 0.0, 0.0);
+
+        // one different gene over two unique genes
+        assert!((distance.between(&genome_0, &genome_1) - 1.0 / 2.0).abs() < f64::EPSILON);
+
+        // one different gene over the two genes of the larger genome
+        let distance = distance.with_normalization(Normalization::LargerGenome);
+        assert!((distance.between(&genome_0, &genome_1) - 1.0 / 2.0).abs() < f64::EPSILON);
+
+        genome_1.feed_forward.clear();
+        genome_1
+            .feed_forward
+            .insert(Connection::new(Id(1), 1.0, Id(2)));
+
+        // two different genes over one gene of the larger genome
+        assert!((distance.between(&genome_0, &genome_1) - 2.0).abs() < f64::EPSILON);
+        assert!(
+            (CompatibilityDistance::with_factors(1.0, 0.0, 0.0).between(&genome_0, &genome_1)
+                - 1.0)
+                .abs()
+                < f64::EPSILON
+        );
+    }
 }
@@ -0,0 +1,775 @@
+use std::collections::HashSet;
+
+use rand::{rngs::SmallRng, seq::index, thread_rng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    genes::{Connection, Gene, Genes, Id, Node},
+    Genome,
+};
+
+/// Scheme by which [`Genome::cross_in_with_strategy`] combines two parents.
+///
+/// Genes present in both parents are called matching, all others disjoint.
+/// `self` is considered the fitter parent.
+/// Every strategy yields a structurally valid child of valid parents, see [`Genome::validate`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CrossoverStrategy {
+    /// Matching genes are taken from either parent with equal chance, disjoint genes only from `self`.
+    #[default]
+    FitterParent,
+    /// Like [`CrossoverStrategy::FitterParent`] but the weights of matching connections and the bias and response of matching nodes are averaged.
+    AverageWeights,
+    /// Meant for parents of equal fitness: matching genes are taken from either parent and every disjoint gene of both parents is inherited with a chance of one half.
+    UniformDisjoint,
+    /// Orders all connections by innovation, i.e. their id, and cuts that order at `points` random positions.
+    /// Matching connections come from `self` and `other` in alternating segments, disjoint genes only from `self`.
+    Multipoint { points: usize },
+}
+
+impl CrossoverStrategy {
+    /// Ids of matching connections that are taken from `other` due to the segments of [`CrossoverStrategy::Multipoint`].
+    fn segments_of_other(
+        &self,
+        own: &Genome,
+        other: &Genome,
+        rng: &mut impl Rng,
+    ) -> HashSet<(Id, Id)> {
+        if let &CrossoverStrategy::Multipoint { points } = self {
+            let mut innovations = own
+                .connections()
+                .chain(other.connections())
+                .map(Connection::id)
+                .collect::<Vec<_>>();
+            innovations.sort_unstable();
+            innovations.dedup();
+
+            let mut cuts =
+                index::sample(rng, innovations.len(), points.min(innovations.len())).into_vec();
+            cuts.sort_unstable();
+
+            innovations
+                .into_iter()
+                .enumerate()
+                .filter(|(position, _)| cuts.iter().filter(|&cut| cut <= position).count() % 2 == 1)
+                .map(|(_, id)| id)
+                .collect()
+        } else {
+            HashSet::new()
+        }
+    }
+
+    fn cross_connections(
+        &self,
+        own: &Genes<Connection>,
+        other: &Genes<Connection>,
+        segments_of_other: &HashSet<(Id, Id)>,
+        rng: &mut impl Rng,
+    ) -> Vec<Connection> {
+        let mut connections = own
+            .iterate_matching_genes(other)
+            .map(|(own, other)| {
+                let mut connection = match self {
+                    CrossoverStrategy::AverageWeights => Connection {
+                        weight: (own.weight + other.weight) / 2.0,
+                        ..*own
+                    },
+                    CrossoverStrategy::Multipoint { .. } => {
+                        if segments_of_other.contains(&own.id()) {
+                            own.recombine(other)
+                        } else {
+                            own.clone()
+                        }
+                    }
+                    _ => {
+                        if rng.gen::<f64>() < 0.5 {
+                            own.clone()
+                        } else {
+                            own.recombine(other)
+                        }
+                    }
+                };
+                connection.enabled = own.inherit_enabled(other, rng);
+                connection
+            })
+            .collect::<Vec<_>>();
+
+        if let CrossoverStrategy::UniformDisjoint = self {
+            for connection in own.iterate_unique_genes(other) {
+                if rng.gen::<f64>() < 0.5 {
+                    connections.push(connection.clone());
+                }
+            }
+        } else {
+            connections.extend(own.difference(other).cloned());
+        }
+
+        connections
+    }
+
+    fn cross_nodes(
+        &self,
+        own: &Genes<Node>,
+        other: &Genes<Node>,
+        rng: &mut impl Rng,
+    ) -> Genes<Node> {
+        own.iterate_matching_genes(other)
+            .map(|(own, other)| {
+                let node = if rng.gen::<f64>() < 0.5 {
+                    own.clone()
+                } else {
+                    own.recombine(other)
+                };
+                if let CrossoverStrategy::AverageWeights = self {
+                    Node {
+                        bias: (own.bias + other.bias) / 2.0,
+                        response: (own.response + other.response) / 2.0,
+                        ..node
+                    }
+                } else {
+                    node
+                }
+            })
+            // disjoint nodes are only kept when referenced by an inherited connection
+            .chain(own.iterate_unique_genes(other).cloned())
+            .collect()
+    }
+}
+
+impl Genome {
+    /// Cross-in another genome.
+    /// For connection genes present in both genomes flip a coin to determine the weight inside the new genome.
+    /// A connection disabled in either genome is disabled in the new genome with a chance of [`Connection::INHERIT_DISABLED_CHANCE`].
+    /// For node genes present in both genomes flip a coin to determine the activation function, bias and response inside the new genome.
+    /// Any structure not present in other is taken over unchanged from `self`.
+    ///
+    /// This is [`CrossoverStrategy::FitterParent`], see [`Genome::cross_in_with_strategy`] for other schemes.
+    pub fn cross_in(&self, other: &Self) -> Self {
+        // Instantiating an RNG for every call might slow things down.
+        self.cross_in_with_rng(other, &mut SmallRng::from_rng(thread_rng()).unwrap())
+    }
+
+    /// Same as [`Genome::cross_in`] but draws all randomness from the supplied `rng`.
+    pub fn cross_in_with_rng(&self, other: &Self, rng: &mut impl Rng) -> Self {
+        self.cross_in_with_strategy(other, &CrossoverStrategy::default(), rng)
+    }
+
+    /// Cross-in another genome according to the given [`CrossoverStrategy`].
+    ///
+    /// Inputs and bias are taken from `self`, hidden nodes are inherited as far as inherited connections reference them.
+    /// Feed-forward connections of `other` that would close a cycle together with the inherited structure are left out
+    /// and hidden nodes left without incoming or outgoing connections are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use set_genome::{CrossoverStrategy, Genome, Parameters};
+    ///
+    /// let parameters = Parameters::default();
+    /// let mut genome_0 = Genome::initialized(&parameters);
+    /// let mut genome_1 = Genome::initialized(&parameters);
+    ///
+    /// for _ in 0..50 {
+    ///     let _ = genome_0.mutate(&parameters);
+    ///     let _ = genome_1.mutate(&parameters);
+    /// }
+    ///
+    /// let offspring = genome_0.cross_in_with_strategy(
+    ///     &genome_1,
+    ///     &CrossoverStrategy::UniformDisjoint,
+    ///     &mut rand::thread_rng(),
+    /// );
+    ///
+    /// assert_eq!(offspring.validate(), Ok(()));
+    /// ```
+    pub fn cross_in_with_strategy(
+        &self,
+        other: &Self,
+        strategy: &CrossoverStrategy,
+        rng: &mut impl Rng,
+    ) -> Self {
+        // invalid parents, e.g. read from disk, are not the fault of the crossover
+        let check = cfg!(debug_assertions) && self.validate().is_ok() && other.validate().is_ok();
+
+        let segments_of_other = strategy.segments_of_other(self, other, rng);
+        let feed_forward = strategy.cross_connections(
+            &self.feed_forward,
+            &other.feed_forward,
+            &segments_of_other,
+            rng,
+        );
+        let recurrent =
+            strategy.cross_connections(&self.recurrent, &other.recurrent, &segments_of_other, rng);
+
+        let referenced = feed_forward
+            .iter()
+            .chain(recurrent.iter())
+            .flat_map(|connection| [connection.input, connection.output])
+            .collect::<HashSet<_>>();
+        let mut hidden = strategy.cross_nodes(&self.hidden, &other.hidden, rng);
+        hidden.retain(|node| referenced.contains(&node.id));
+
+        let mut offspring = Genome {
+            hidden,
+            // outputs are identical in both genomes apart from their bias and response
+            outputs: strategy.cross_nodes(&self.outputs, &other.outputs, rng),
+            feed_forward: feed_forward.into_iter().collect(),
+            recurrent: recurrent.into_iter().collect(),
+            // use input and bias from fitter, but they should be identical with weaker
+            inputs: self.inputs.clone(),
+            bias: self.bias.clone(),
+        };
+
+        // parents that ordered their nodes differently can combine into a cycle,
+        // the connections of `self` form none so only connections of `other` are removed
+        offspring.break_feed_forward_cycles(|connection| self.feed_forward.contains(connection));
+        offspring.remove_dangling_hidden_nodes();
+        offspring.order_hidden_nodes();
+
+        if check {
+            if let Err(violations) = offspring.validate() {
+                panic!(
+                    "offspring is invalid after crossover with {:?}: {:?}",
+                    strategy, violations
+                );
+            }
+        }
+
+        offspring
+    }
+
+    /// Recombines `self` with any number of other genomes, e.g. for estimation-of-distribution style algorithms.
+    ///
+    /// The structure of the offspring is identical to `self`.
+    /// The attributes of every gene, i.e. weights of connections and activation, bias and response of nodes, come from one of the genomes carrying that gene,
+    /// chosen with a chance proportional to its weight among all carriers.
+    /// `weights`, usually the fitness of every parent, lists the weight of `self` followed by those of `others` and must not be negative.
+    /// A connection disabled in any carrier is disabled in the offspring with a chance of [`Connection::INHERIT_DISABLED_CHANCE`].
+    ///
+    /// # Panics
+    ///
+    /// If `weights` does not hold exactly one weight per parent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use set_genome::{Genome, Parameters};
+    ///
+    /// let parameters = Parameters::basic(3, 2);
+    /// let parents = (0..4)
+    ///     .map(|_| Genome::initialized(&parameters))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let offspring = parents[0].cross_in_many(
+    ///     &[&parents[1], &parents[2], &parents[3]],
+    ///     &[4.0, 3.0, 2.0, 1.0],
+    /// );
+    ///
+    /// assert_eq!(offspring.feed_forward, parents[0].feed_forward);
+    /// ```
+    pub fn cross_in_many(&self, others: &[&Genome], weights: &[f64]) -> Self {
+        self.cross_in_many_with_rng(
+            others,
+            weights,
+            &mut SmallRng::from_rng(thread_rng()).unwrap(),
+        )
+    }
+
+    /// Same as [`Genome::cross_in_many`] but draws all randomness from the supplied `rng`.
+    pub fn cross_in_many_with_rng(
+        &self,
+        others: &[&Genome],
+        weights: &[f64],
+        rng: &mut impl Rng,
+    ) -> Self {
+        assert_eq!(
+            weights.len(),
+            others.len() + 1,
+            "expected one weight for self and one per other genome"
+        );
+
+        let mut offspring = Genome {
+            feed_forward: Self::cross_in_many_connections(
+                &self.feed_forward,
+                &others
+                    .iter()
+                    .map(|other| &other.feed_forward)
+                    .collect::<Vec<_>>(),
+                weights,
+                rng,
+            ),
+            recurrent: Self::cross_in_many_connections(
+                &self.recurrent,
+                &others
+                    .iter()
+                    .map(|other| &other.recurrent)
+                    .collect::<Vec<_>>(),
+                weights,
+                rng,
+            ),
+            hidden: self.hidden.cross_in_many(
+                &others.iter().map(|other| &other.hidden).collect::<Vec<_>>(),
+                weights,
+                rng,
+            ),
+            outputs: self.outputs.cross_in_many(
+                &others
+                    .iter()
+                    .map(|other| &other.outputs)
+                    .collect::<Vec<_>>(),
+                weights,
+                rng,
+            ),
+            inputs: self.inputs.clone(),
+            bias: self.bias.clone(),
+        };
+        offspring.order_hidden_nodes();
+        offspring
+    }
+
+    fn cross_in_many_connections(
+        own: &Genes<Connection>,
+        others: &[&Genes<Connection>],
+        weights: &[f64],
+        rng: &mut impl Rng,
+    ) -> Genes<Connection> {
+        own.cross_in_many(others, weights, rng)
+            .drain()
+            .map(|mut connection| {
+                let all_enabled = others
+                    .iter()
+                    .filter_map(|other| other.get(&connection))
+                    .chain(own.get(&connection))
+                    .all(|carrier| carrier.enabled);
+                connection.enabled =
+                    all_enabled || rng.gen::<f64>() >= Connection::INHERIT_DISABLED_CHANCE;
+                connection
+            })
+            .collect()
+    }
+
+    /// Is true when the feed-forward connections contain a cycle.
+    pub fn has_feed_forward_cycle(&self) -> bool {
+        let depths = self.feed_forward_depths();
+        self.nodes().any(|node| !depths.contains_key(&node.id))
+    }
+
+    /// Deterministically removes feed-forward connections until no cycle is left and returns the ids of the removed connections.
+    ///
+    /// Connections are kept in the order of their ids, every connection closing a cycle with those kept before is removed.
+    /// Hidden nodes left without incoming or outgoing connections are removed as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use set_genome::{Connection, Genome, Parameters};
+    ///
+    /// let mut genome = Genome::initialized(&Parameters::basic(1, 1));
+    /// let connection = genome.feed_forward.iter().next().cloned().unwrap();
+    ///
+    /// genome
+    ///     .feed_forward
+    ///     .insert(Connection::new(connection.output, 1.0, connection.input));
+    /// assert!(genome.has_feed_forward_cycle());
+    ///
+    /// genome.repair_feed_forward_cycles();
+    /// assert!(!genome.has_feed_forward_cycle());
+    /// ```
+    pub fn repair_feed_forward_cycles(&mut self) -> Vec<(Id, Id)> {
+        let removed = self.break_feed_forward_cycles(|_| false);
+        self.remove_dangling_hidden_nodes();
+        self.order_hidden_nodes();
+        removed
+    }
+
+    /// Keeps connections for which `prefer` is true first, then all others, both in the order of their ids.
+    fn break_feed_forward_cycles(&mut self, prefer: impl Fn(&Connection) -> bool) -> Vec<(Id, Id)> {
+        if !self.has_feed_forward_cycle() {
+            return Vec::new();
+        }
+
+        let mut connections = self.feed_forward.drain().collect::<Vec<_>>();
+        connections.sort_unstable_by_key(|connection| (!prefer(connection), connection.id()));
+
+        let mut removed = Vec::new();
+        for connection in connections {
+            if connection.input == connection.output
+                || self.would_form_cycle(
+                    &Node::input(connection.input, 0),
+                    &Node::input(connection.output, 0),
+                )
+            {
+                removed.push(connection.id());
+            } else {
+                self.feed_forward.insert(connection);
+            }
+        }
+        removed
+    }
+
+    /// Removes hidden nodes without incoming or outgoing connections together with their connections, until none are left.
+    fn remove_dangling_hidden_nodes(&mut self) {
+        loop {
+            let dangling = self
+                .hidden
+                .iter()
+                .filter(|node| {
+                    !self.connections().any(|c| c.output == node.id)
+                        || !self.connections().any(|c| c.input == node.id)
+                })
+                .map(|node| node.id)
+                .collect::<HashSet<_>>();
+
+            if dangling.is_empty() {
+                break;
+            }
+
+            self.hidden.retain(|node| !dangling.contains(&node.id));
+            let attached = |connection: &Connection| {
+                !dangling.contains(&connection.input) && !dangling.contains(&connection.output)
+            };
+            self.feed_forward.retain(attached);
+            self.recurrent.retain(attached);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, seq::index, SeedableRng};
+
+    use crate::{
+        activations::Activation,
+        genes::{Connection, Genes, Id, Node},
+        CrossoverStrategy, Genome, Parameters,
+    };
+
+    const STRATEGIES: [CrossoverStrategy; 5] = [
+        CrossoverStrategy::FitterParent,
+        CrossoverStrategy::AverageWeights,
+        CrossoverStrategy::UniformDisjoint,
+        CrossoverStrategy::Multipoint { points: 1 },
+        CrossoverStrategy::Multipoint { points: 5 },
+    ];
+
+    fn parents() -> (Genome, Genome) {
+        let inputs = Genes([Node::input(Id(0), 0)].iter().cloned().collect());
+        let outputs: Genes<Node> = Genes(
+            [Node::output(Id(1), 0, Activation::Linear)]
+                .iter()
+                .cloned()
+                .collect(),
+        );
+
+        // both parents share the connection 0 -> 1 but connect their hidden nodes in opposite order
+        let parent_0 = Genome {
+            inputs: inputs.clone(),
+            outputs: outputs.clone(),
+            hidden: Genes(
+                [
+                    Node::hidden(Id(2), Activation::Linear),
+                    Node::hidden(Id(3), Activation::Linear),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            feed_forward: Genes(
+                [
+                    Connection::new(Id(0), 0.2, Id(1)),
+                    Connection::new(Id(0), 1.0, Id(2)),
+                    Connection::new(Id(2), 1.0, Id(3)),
+                    Connection::new(Id(3), 1.0, Id(1)),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            ..Default::default()
+        };
+        let parent_1 = Genome {
+            inputs,
+            outputs,
+            hidden: Genes(
+                [
+                    Node::hidden(Id(2), Activation::Tanh),
+                    Node::hidden(Id(3), Activation::Tanh),
+                    Node::hidden(Id(4), Activation::Tanh),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            feed_forward: Genes(
+                [
+                    Connection::new(Id(0), 0.6, Id(1)),
+                    Connection::new(Id(0), 1.0, Id(3)),
+                    Connection::new(Id(3), 1.0, Id(2)),
+                    Connection::new(Id(2), 1.0, Id(1)),
+                    Connection::new(Id(0), 1.0, Id(4)),
+                    Connection::new(Id(4), 1.0, Id(1)),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            ..Default::default()
+        };
+
+        (parent_0, parent_1)
+    }
+
+    #[test]
+    fn every_strategy_yields_valid_offspring() {
+        let (parent_0, parent_1) = parents();
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        for strategy in &STRATEGIES {
+            for _ in 0..100 {
+                let offspring = parent_0.cross_in_with_strategy(&parent_1, strategy, &mut rng);
+                assert_eq!(offspring.validate(), Ok(()), "{:?}", strategy);
+
+                let offspring = parent_1.cross_in_with_strategy(&parent_0, strategy, &mut rng);
+                assert_eq!(offspring.validate(), Ok(()), "{:?}", strategy);
+            }
+        }
+    }
+
+    #[test]
+    fn every_strategy_yields_valid_offspring_of_mutated_genomes() {
+        let parameters = Parameters::default();
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        for strategy in &STRATEGIES {
+            let mut genome_0 = Genome::initialized_with_rng(&parameters, &mut rng);
+            let mut genome_1 = Genome::initialized_with_rng(&parameters, &mut rng);
+
+            for _ in 0..100 {
+                let _ = genome_0.mutate_with_rng(&parameters, &mut rng);
+                let _ = genome_1.mutate_with_rng(&parameters, &mut rng);
+
+                let offspring = genome_0.cross_in_with_strategy(&genome_1, strategy, &mut rng);
+                assert_eq!(offspring.validate(), Ok(()), "{:?}", strategy);
+            }
+        }
+    }
+
+    #[test]
+    fn offspring_of_random_parents_is_valid() {
+        let parameters = Parameters::default();
+        let mut rng = SmallRng::seed_from_u64(7);
+
+        // a pool of independently evolved genomes orders its hidden nodes in many different ways
+        let mut pool = (0..20)
+            .map(|_| Genome::initialized_with_rng(&parameters, &mut rng))
+            .collect::<Vec<_>>();
+
+        for _ in 0..100 {
+            for genome in &mut pool {
+                let _ = genome.mutate_with_rng(&parameters, &mut rng);
+            }
+
+            for _ in 0..20 {
+                let pair = index::sample(&mut rng, pool.len(), 2);
+                let (parent_0, parent_1) = (&pool[pair.index(0)], &pool[pair.index(1)]);
+
+                for strategy in &STRATEGIES {
+                    let offspring = parent_0.cross_in_with_strategy(parent_1, strategy, &mut rng);
+                    assert_eq!(offspring.validate(), Ok(()), "{:?}", strategy);
+                    assert!(!offspring.has_feed_forward_cycle());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn crossover_is_deterministic() {
+        let (parent_0, parent_1) = parents();
+
+        for strategy in &STRATEGIES {
+            assert_eq!(
+                parent_0.cross_in_with_strategy(
+                    &parent_1,
+                    strategy,
+                    &mut SmallRng::seed_from_u64(42)
+                ),
+                parent_0.cross_in_with_strategy(
+                    &parent_1,
+                    strategy,
+                    &mut SmallRng::seed_from_u64(42)
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn repair_cycle_by_id_order() {
+        let (parent_0, parent_1) = parents();
+
+        let mut genome = parent_0.clone();
+        genome
+            .feed_forward
+            .extend(parent_1.feed_forward.iter().cloned());
+        genome.hidden.insert(Node::hidden(Id(4), Activation::Tanh));
+        assert!(genome.has_feed_forward_cycle());
+
+        // 2 -> 3 is kept before 3 -> 2 is considered
+        assert_eq!(genome.repair_feed_forward_cycles(), vec![(Id(3), Id(2))]);
+        assert_eq!(genome.validate(), Ok(()));
+        assert!(genome.repair_feed_forward_cycles().is_empty());
+    }
+
+    #[test]
+    fn prefer_structure_of_fitter_parent_when_breaking_cycles() {
+        let (parent_0, parent_1) = parents();
+
+        let mut genome = parent_1.clone();
+        genome
+            .feed_forward
+            .extend(parent_0.feed_forward.iter().cloned());
+
+        assert_eq!(
+            genome
+                .break_feed_forward_cycles(|connection| parent_1.feed_forward.contains(connection)),
+            vec![(Id(2), Id(3))]
+        );
+    }
+
+    #[test]
+    fn cross_in_many_keeps_structure_of_self() {
+        let (parent_0, parent_1) = parents();
+        let mut parent_2 = parent_1.clone();
+        parent_2
+            .feed_forward
+            .replace(Connection::new(Id(0), 0.8, Id(1)));
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        let offspring =
+            parent_0.cross_in_many_with_rng(&[&parent_1, &parent_2], &[0.0, 0.0, 1.0], &mut rng);
+
+        assert_eq!(offspring.feed_forward, parent_0.feed_forward);
+        assert_eq!(offspring.hidden, parent_0.hidden);
+        // only 0 -> 1 and the hidden nodes are carried by all parents
+        assert_eq!(
+            offspring
+                .feed_forward
+                .get(&Connection::new(Id(0), 0.0, Id(1)))
+                .unwrap()
+                .weight,
+            0.8
+        );
+        assert!(offspring
+            .hidden
+            .iter()
+            .all(|node| node.activation == Activation::Tanh));
+        assert!(offspring
+            .feed_forward
+            .iter()
+            .filter(|connection| connection.id() != (Id(0), Id(1)))
+            .all(|connection| connection.weight == 1.0));
+        assert_eq!(offspring.validate(), Ok(()));
+    }
+
+    #[test]
+    fn cross_in_many_weights_parents() {
+        let (parent_0, parent_1) = parents();
+        let mut rng = SmallRng::seed_from_u64(42);
+        let shared = Connection::new(Id(0), 0.0, Id(1));
+
+        let from_parent_1 = (0..1000)
+            .filter(|_| {
+                parent_0
+                    .cross_in_many_with_rng(&[&parent_1], &[1.0, 3.0], &mut rng)
+                    .feed_forward
+                    .get(&shared)
+                    .unwrap()
+                    .weight
+                    == 0.6
+            })
+            .count();
+        assert!((650..850).contains(&from_parent_1));
+
+        // without any weight everything comes from self
+        let offspring = parent_0.cross_in_many_with_rng(&[&parent_1], &[0.0, 0.0], &mut rng);
+        assert_eq!(offspring.feed_forward.get(&shared).unwrap().weight, 0.2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn cross_in_many_needs_weight_per_parent() {
+        let (parent_0, parent_1) = parents();
+
+        parent_0.cross_in_many(&[&parent_1], &[1.0]);
+    }
+
+    #[test]
+    fn fitter_parent_keeps_own_structure() {
+        let (parent_0, parent_1) = parents();
+
+        let offspring = parent_0.cross_in_with_strategy(
+            &parent_1,
+            &CrossoverStrategy::FitterParent,
+            &mut SmallRng::seed_from_u64(42),
+        );
+
+        assert_eq!(offspring.feed_forward, parent_0.feed_forward);
+        assert_eq!(offspring.hidden, parent_0.hidden);
+    }
+
+    #[test]
+    fn average_matching_weights() {
+        let (parent_0, parent_1) = parents();
+
+        let offspring = parent_0.cross_in_with_strategy(
+            &parent_1,
+            &CrossoverStrategy::AverageWeights,
+            &mut SmallRng::seed_from_u64(42),
+        );
+
+        let shared = offspring
+            .feed_forward
+            .get(&Connection::new(Id(0), 0.0, Id(1)))
+            .unwrap();
+        assert!((shared.weight - 0.4).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn uniform_disjoint_inherits_from_both_parents() {
+        let (parent_0, parent_1) = parents();
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        let inherited_from_1 = (0..100)
+            .map(|_| {
+                parent_0.cross_in_with_strategy(
+                    &parent_1,
+                    &CrossoverStrategy::UniformDisjoint,
+                    &mut rng,
+                )
+            })
+            .filter(|offspring| offspring.contains(Id(4)))
+            .count();
+
+        assert!(inherited_from_1 > 0 && inherited_from_1 < 100);
+    }
+
+    #[test]
+    fn multipoint_without_cuts_takes_self() {
+        let (parent_0, parent_1) = parents();
+
+        let offspring = parent_0.cross_in_with_strategy(
+            &parent_1,
+            &CrossoverStrategy::Multipoint { points: 0 },
+            &mut SmallRng::seed_from_u64(42),
+        );
+
+        assert_eq!(
+            offspring
+                .feed_forward
+                .get(&Connection::new(Id(0), 0.0, Id(1)))
+                .unwrap()
+                .weight,
+            0.2
+        );
+    }
+}
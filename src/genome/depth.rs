@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use crate::{genes::Id, Genome};
+
+/// The feed-forward structure of a [`Genome`] grouped by depth, see [`Genome::layers`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Layers {
+    /// Depth of every node, i.e. the length of the longest feed-forward path leading to it.
+    pub depths: HashMap<Id, usize>,
+    /// Ids of all nodes per depth, sorted within each layer.
+    pub layers: Vec<Vec<Id>>,
+    /// Length of the longest feed-forward path ending in an output node.
+    pub depth: usize,
+}
+
+impl Layers {
+    /// Number of nodes in the widest layer.
+    pub fn width(&self) -> usize {
+        self.layers.iter().map(Vec::len).max().unwrap_or(0)
+    }
+}
+
+impl Genome {
+    /// Computes the depth of every node, i.e. the length of the longest feed-forward path leading to it.
+    ///
+    /// Nodes without incoming feed-forward connections, like inputs and the bias, have depth zero.
+    /// Nodes that are part of or behind a feed-forward cycle are left out.
+    pub(crate) fn feed_forward_depths(&self) -> HashMap<Id, usize> {
+        let mut pending: HashMap<Id, usize> = HashMap::new();
+        let mut dependents: HashMap<Id, Vec<Id>> = HashMap::new();
+        for connection in self.feed_forward.iter() {
+            *pending.entry(connection.output).or_default() += 1;
+            dependents
+                .entry(connection.input)
+                .or_default()
+                .push(connection.output);
+        }
+
+        let mut depths: HashMap<Id, usize> = HashMap::new();
+        let mut ready = self
+            .nodes()
+            .map(|node| node.id)
+            .filter(|id| !pending.contains_key(id))
+            .collect::<Vec<_>>();
+        for &id in &ready {
+            depths.insert(id, 0);
+        }
+
+        // Kahn's algorithm, a node's depth is final once all its inputs are resolved
+        while let Some(node) = ready.pop() {
+            let depth = depths[&node];
+            for &dependent in dependents.get(&node).into_iter().flatten() {
+                let dependent_depth = depths.entry(dependent).or_default();
+                *dependent_depth = (*dependent_depth).max(depth + 1);
+                let count = pending.get_mut(&dependent).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(dependent);
+                }
+            }
+        }
+
+        depths.retain(|id, _| pending.get(id).copied().unwrap_or(0) == 0);
+        depths
+    }
+
+    /// Analyzes the depth of all nodes in the feed-forward connections.
+    ///
+    /// Every node whose feed-forward inputs all sit in earlier layers can be computed once those are done.
+    /// Disabled connections are considered as well, so toggling connections does not reshape the layers.
+    /// Nodes that are part of or behind a feed-forward cycle are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use set_genome::{Genome, Mutations, Parameters, activations::Activation};
+    ///
+    /// let mut genome = Genome::initialized(&Parameters::basic(2, 1));
+    ///
+    /// Mutations::add_node(&[Activation::Tanh], &mut genome, &mut rand::thread_rng()).unwrap();
+    ///
+    /// let layers = genome.layers();
+    ///
+    /// assert_eq!(layers.depth, 2);
+    /// assert_eq!(layers.layers[1], vec![genome.hidden.iter().next().unwrap().id]);
+    /// ```
+    pub fn layers(&self) -> Layers {
+        let depths = self.feed_forward_depths();
+
+        let mut layers = vec![Vec::new(); depths.values().max().map_or(0, |max| max + 1)];
+        for (&id, &depth) in &depths {
+            layers[depth].push(id);
+        }
+        for layer in &mut layers {
+            layer.sort_unstable();
+        }
+
+        let depth = self
+            .outputs
+            .iter()
+            .filter_map(|node| depths.get(&node.id))
+            .max()
+            .copied()
+            .unwrap_or(0);
+
+        Layers {
+            depths,
+            layers,
+            depth,
+        }
+    }
+
+    /// Sets the `order` of every hidden node to its feed-forward depth, see [`crate::Node`] for how nodes are sorted.
+    ///
+    /// Mutations applied via [`crate::Mutations::apply`], crossover and pruning keep the order up to date.
+    /// Genomes altered by hand or deserialized from older versions should call this before relying on the sorted order of hidden nodes.
+    pub fn order_hidden_nodes(&mut self) {
+        let depths = self.feed_forward_depths();
+
+        self.hidden = self
+            .hidden
+            .drain()
+            .map(|mut node| {
+                if let Some(&depth) = depths.get(&node.id) {
+                    node.order = depth;
+                }
+                node
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        activations::Activation,
+        genes::{Connection, Genes, Id, Node},
+        Genome,
+    };
+
+    fn layered_genome() -> Genome {
+        Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            hidden: Genes(
+                [
+                    Node::hidden(Id(2), Activation::Linear),
+                    Node::hidden(Id(3), Activation::Linear),
+                    Node::hidden(Id(4), Activation::Linear),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            outputs: Genes(
+                [Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [
+                    Connection::new(Id(0), 1.0, Id(4)),
+                    Connection::new(Id(4), 1.0, Id(2)),
+                    Connection::new(Id(0), 1.0, Id(3)),
+                    Connection::new(Id(2), 1.0, Id(1)),
+                    Connection::new(Id(3), 1.0, Id(1)),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn order_hidden_nodes_by_depth_then_id() {
+        let mut genome = layered_genome();
+
+        genome.order_hidden_nodes();
+
+        assert_eq!(
+            genome
+                .hidden
+                .as_ordered_vec()
+                .into_iter()
+                .map(|node| (node.id, node.order))
+                .collect::<Vec<_>>(),
+            vec![(Id(3), 1), (Id(4), 1), (Id(2), 2)]
+        );
+    }
+
+    #[test]
+    fn group_nodes_into_layers() {
+        let layers = layered_genome().layers();
+
+        assert_eq!(
+            layers.layers,
+            vec![vec![Id(0)], vec![Id(3), Id(4)], vec![Id(2)], vec![Id(1)]]
+        );
+        assert_eq!(layers.depths[&Id(2)], 2);
+        assert_eq!(layers.depth, 3);
+        assert_eq!(layers.width(), 2);
+    }
+}
@@ -0,0 +1,467 @@
+use std::{collections::HashMap, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    genes::{Activation, Connection, Genes, Id, Node},
+    DotOptions, Genome,
+};
+
+/// A weight that differs between matching connections, see [`GenomeDiff`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WeightChange {
+    pub connection: (Id, Id),
+    pub recurrent: bool,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl WeightChange {
+    pub fn delta(&self) -> f64 {
+        self.after - self.before
+    }
+}
+
+/// An activation that differs between matching nodes, see [`GenomeDiff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivationChange {
+    pub node: Id,
+    pub before: Activation,
+    pub after: Activation,
+}
+
+/// A connection that got disabled or enabled, see [`GenomeDiff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnabledChange {
+    pub connection: (Id, Id),
+    pub recurrent: bool,
+    pub before: bool,
+    pub after: bool,
+}
+
+/// A `bias` or `response` that differs between matching nodes, see [`GenomeDiff`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NodeParameterChange {
+    pub node: Id,
+    pub bias_before: f64,
+    pub bias_after: f64,
+    pub response_before: f64,
+    pub response_after: f64,
+}
+
+/// Everything that differs between two genomes, see [`Genome::diff`].
+///
+/// All lists are sorted by id.
+/// It prints as one line per change and can be highlighted in the DOT output via [`DotOptions::with_highlighted_diff`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GenomeDiff {
+    pub added_nodes: Vec<Id>,
+    pub removed_nodes: Vec<Id>,
+    pub added_feed_forward: Vec<(Id, Id)>,
+    pub removed_feed_forward: Vec<(Id, Id)>,
+    pub added_recurrent: Vec<(Id, Id)>,
+    pub removed_recurrent: Vec<(Id, Id)>,
+    pub weight_changes: Vec<WeightChange>,
+    pub activation_changes: Vec<ActivationChange>,
+    #[serde(default)]
+    pub enabled_changes: Vec<EnabledChange>,
+    #[serde(default)]
+    pub node_parameter_changes: Vec<NodeParameterChange>,
+}
+
+impl GenomeDiff {
+    /// Is true when both genomes are identical in structure, weights, enabled flags, activations and node parameters.
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.added_feed_forward.is_empty()
+            && self.removed_feed_forward.is_empty()
+            && self.added_recurrent.is_empty()
+            && self.removed_recurrent.is_empty()
+            && self.weight_changes.is_empty()
+            && self.activation_changes.is_empty()
+            && self.enabled_changes.is_empty()
+            && self.node_parameter_changes.is_empty()
+    }
+
+    fn compare_connections(
+        &mut self,
+        before: &Genes<Connection>,
+        after: &Genes<Connection>,
+        recurrent: bool,
+    ) {
+        let mut added = after
+            .difference(before)
+            .map(Connection::id)
+            .collect::<Vec<_>>();
+        let mut removed = before
+            .difference(after)
+            .map(Connection::id)
+            .collect::<Vec<_>>();
+        let mut changes = before
+            .iterate_matching_genes(after)
+            .filter(|(before, after)| before.weight != after.weight)
+            .map(|(before, after)| WeightChange {
+                connection: before.id(),
+                recurrent,
+                before: before.weight,
+                after: after.weight,
+            })
+            .collect::<Vec<_>>();
+        let mut enabled_changes = before
+            .iterate_matching_genes(after)
+            .filter(|(before, after)| before.enabled != after.enabled)
+            .map(|(before, after)| EnabledChange {
+                connection: before.id(),
+                recurrent,
+                before: before.enabled,
+                after: after.enabled,
+            })
+            .collect::<Vec<_>>();
+
+        added.sort_unstable();
+        removed.sort_unstable();
+        changes.sort_unstable_by_key(|change| change.connection);
+        enabled_changes.sort_unstable_by_key(|change| change.connection);
+
+        if recurrent {
+            self.added_recurrent = added;
+            self.removed_recurrent = removed;
+        } else {
+            self.added_feed_forward = added;
+            self.removed_feed_forward = removed;
+        }
+        self.weight_changes.extend(changes);
+        self.enabled_changes.extend(enabled_changes);
+    }
+}
+
+impl fmt::Display for GenomeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for id in &self.added_nodes {
+            writeln!(f, "+ node {}", id.0)?;
+        }
+        for id in &self.removed_nodes {
+            writeln!(f, "- node {}", id.0)?;
+        }
+        for (kind, added, removed) in [
+            (
+                "feed-forward",
+                &self.added_feed_forward,
+                &self.removed_feed_forward,
+            ),
+            ("recurrent", &self.added_recurrent, &self.removed_recurrent),
+        ] {
+            for (input, output) in added {
+                writeln!(f, "+ {} {} -> {}", kind, input.0, output.0)?;
+            }
+            for (input, output) in removed {
+                writeln!(f, "- {} {} -> {}", kind, input.0, output.0)?;
+            }
+        }
+        for change in &self.weight_changes {
+            writeln!(
+                f,
+                "~ {} {} -> {}: {} -> {} ({:+})",
+                if change.recurrent {
+                    "recurrent"
+                } else {
+                    "feed-forward"
+                },
+                change.connection.0 .0,
+                change.connection.1 .0,
+                change.before,
+                change.after,
+                change.delta()
+            )?;
+        }
+        for change in &self.enabled_changes {
+            writeln!(
+                f,
+                "~ {} {} -> {}: {}",
+                if change.recurrent {
+                    "recurrent"
+                } else {
+                    "feed-forward"
+                },
+                change.connection.0 .0,
+                change.connection.1 .0,
+                if change.after { "enabled" } else { "disabled" }
+            )?;
+        }
+        for change in &self.activation_changes {
+            writeln!(
+                f,
+                "~ node {}: {:?} -> {:?}",
+                change.node.0, change.before, change.after
+            )?;
+        }
+        for change in &self.node_parameter_changes {
+            writeln!(
+                f,
+                "~ node {}: bias {} -> {}, response {} -> {}",
+                change.node.0,
+                change.bias_before,
+                change.bias_after,
+                change.response_before,
+                change.response_after
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Genome {
+    /// Lists what changed from `self` to `other`, e.g. from a parent to its offspring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use set_genome::{Genome, Mutations, Parameters, activations::Activation};
+    ///
+    /// let parent = Genome::initialized(&Parameters::basic(2, 1));
+    /// let mut child = parent.clone();
+    ///
+    /// Mutations::add_node(&[Activation::Tanh], &mut child, &mut rand::thread_rng()).unwrap();
+    ///
+    /// let diff = parent.diff(&child);
+    ///
+    /// assert_eq!(diff.added_nodes.len(), 1);
+    /// assert_eq!(diff.added_feed_forward.len(), 2);
+    /// print!("{}", diff);
+    /// ```
+    pub fn diff(&self, other: &Self) -> GenomeDiff {
+        let before: HashMap<Id, &Node> = self.nodes().map(|node| (node.id, node)).collect();
+        let after: HashMap<Id, &Node> = other.nodes().map(|node| (node.id, node)).collect();
+
+        let mut added_nodes = after
+            .keys()
+            .filter(|id| !before.contains_key(id))
+            .copied()
+            .collect::<Vec<_>>();
+        let mut removed_nodes = before
+            .keys()
+            .filter(|id| !after.contains_key(id))
+            .copied()
+            .collect::<Vec<_>>();
+        let mut activation_changes = before
+            .iter()
+            .filter_map(|(id, before)| {
+                after
+                    .get(id)
+                    .filter(|after| after.activation != before.activation)
+                    .map(|after| ActivationChange {
+                        node: *id,
+                        before: before.activation,
+                        after: after.activation,
+                    })
+            })
+            .collect::<Vec<_>>();
+        let mut node_parameter_changes = before
+            .iter()
+            .filter_map(|(id, before)| {
+                after
+                    .get(id)
+                    .filter(|after| after.bias != before.bias || after.response != before.response)
+                    .map(|after| NodeParameterChange {
+                        node: *id,
+                        bias_before: before.bias,
+                        bias_after: after.bias,
+                        response_before: before.response,
+                        response_after: after.response,
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        added_nodes.sort_unstable();
+        removed_nodes.sort_unstable();
+        activation_changes.sort_unstable_by_key(|change| change.node);
+        node_parameter_changes.sort_unstable_by_key(|change| change.node);
+
+        let mut diff = GenomeDiff {
+            added_nodes,
+            removed_nodes,
+            activation_changes,
+            node_parameter_changes,
+            ..Default::default()
+        };
+        diff.compare_connections(&self.feed_forward, &other.feed_forward, false);
+        diff.compare_connections(&self.recurrent, &other.recurrent, true);
+        diff
+    }
+}
+
+impl DotOptions {
+    /// Highlights everything added or changed according to `diff`, removed genes are not part of the rendered genome.
+    pub fn with_highlighted_diff(self, diff: &GenomeDiff) -> Self {
+        self.with_highlighted(
+            diff.added_nodes
+                .iter()
+                .copied()
+                .chain(diff.activation_changes.iter().map(|change| change.node))
+                .chain(diff.node_parameter_changes.iter().map(|change| change.node)),
+            diff.added_feed_forward
+                .iter()
+                .chain(diff.added_recurrent.iter())
+                .copied()
+                .chain(diff.weight_changes.iter().map(|change| change.connection))
+                .chain(diff.enabled_changes.iter().map(|change| change.connection)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        activations::Activation,
+        genes::{Connection, Genes, Id, Node},
+        DotOptions, Genome,
+    };
+
+    use super::{ActivationChange, EnabledChange, NodeParameterChange, WeightChange};
+
+    fn parent() -> Genome {
+        Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            hidden: Genes(
+                [Node::hidden(Id(2), Activation::Tanh)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            outputs: Genes(
+                [Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [
+                    Connection::new(Id(0), 0.5, Id(2)),
+                    Connection::new(Id(2), 0.5, Id(1)),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn diff_identical_genomes() {
+        assert!(parent().diff(&parent()).is_empty());
+        assert_eq!(parent().diff(&parent()).to_string(), "");
+    }
+
+    #[test]
+    fn diff_changed_genome() {
+        let parent = parent();
+        let mut child = parent.clone();
+        child.hidden.replace(Node::hidden(Id(2), Activation::Relu));
+        child
+            .feed_forward
+            .replace(Connection::new(Id(0), 0.75, Id(2)));
+        child
+            .feed_forward
+            .insert(Connection::new(Id(0), 1.0, Id(1)));
+        child.recurrent.insert(Connection::new(Id(1), 1.0, Id(2)));
+
+        let diff = parent.diff(&child);
+
+        assert!(diff.added_nodes.is_empty());
+        assert_eq!(diff.added_feed_forward, vec![(Id(0), Id(1))]);
+        assert_eq!(diff.added_recurrent, vec![(Id(1), Id(2))]);
+        assert_eq!(
+            diff.weight_changes,
+            vec![WeightChange {
+                connection: (Id(0), Id(2)),
+                recurrent: false,
+                before: 0.5,
+                after: 0.75,
+            }]
+        );
+        assert_eq!(
+            diff.activation_changes,
+            vec![ActivationChange {
+                node: Id(2),
+                before: Activation::Tanh,
+                after: Activation::Relu,
+            }]
+        );
+        assert_eq!(
+            diff.to_string(),
+            "+ feed-forward 0 -> 1\n+ recurrent 1 -> 2\n~ feed-forward 0 -> 2: 0.5 -> 0.75 (+0.25)\n~ node 2: Tanh -> Relu\n"
+        );
+
+        let reverse = child.diff(&parent);
+        assert_eq!(reverse.removed_feed_forward, vec![(Id(0), Id(1))]);
+        assert_eq!(reverse.removed_recurrent, vec![(Id(1), Id(2))]);
+    }
+
+    #[test]
+    fn diff_enabled_flags_and_node_parameters() {
+        let parent = parent();
+        let mut child = parent.clone();
+        child.feed_forward.replace(Connection {
+            enabled: false,
+            ..Connection::new(Id(0), 0.5, Id(2))
+        });
+        child.hidden.replace(Node {
+            bias: 0.5,
+            response: 2.0,
+            ..Node::hidden(Id(2), Activation::Tanh)
+        });
+
+        let diff = parent.diff(&child);
+
+        assert!(!diff.is_empty());
+        assert!(diff.weight_changes.is_empty());
+        assert!(diff.activation_changes.is_empty());
+        assert_eq!(
+            diff.enabled_changes,
+            vec![EnabledChange {
+                connection: (Id(0), Id(2)),
+                recurrent: false,
+                before: true,
+                after: false,
+            }]
+        );
+        assert_eq!(
+            diff.node_parameter_changes,
+            vec![NodeParameterChange {
+                node: Id(2),
+                bias_before: 0.0,
+                bias_after: 0.5,
+                response_before: 1.0,
+                response_after: 2.0,
+            }]
+        );
+        assert_eq!(
+            diff.to_string(),
+            "~ feed-forward 0 -> 2: disabled\n~ node 2: bias 0 -> 0.5, response 1 -> 2\n"
+        );
+
+        let dot =
+            Genome::dot_with_options(&child, &DotOptions::default().with_highlighted_diff(&diff));
+        assert!(dot.contains("\t\t2 [label=Tanh color="));
+        assert!(dot.contains("0 -> 2 [label=\"\" arrowsize=2.0"));
+    }
+
+    #[test]
+    fn highlight_diff() {
+        let parent = parent();
+        let mut child = parent.clone();
+        child
+            .feed_forward
+            .replace(Connection::new(Id(2), -0.5, Id(1)));
+
+        let dot = Genome::dot_with_options(
+            &child,
+            &DotOptions::default().with_highlighted_diff(&parent.diff(&child)),
+        );
+
+        assert!(dot.contains("2 -> 1 [label=\"\" arrowsize=2.0"));
+        assert!(dot.contains("0 -> 2 [label=\"\" arrowsize=0.525"));
+    }
+}
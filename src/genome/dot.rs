@@ -0,0 +1,679 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    genes::{Connection, Id, Node},
+    Genome,
+};
+
+/// How nodes are named in the [DOT][1] output, see [`DotOptions::with_node_ids`].
+///
+/// [1]: https://www.graphviz.org/doc/info/lang.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeIds {
+    /// The raw `u64` of every [`Id`].
+    Raw,
+    /// Short names by position: `i0`, `i1`, ... for inputs, `b` for the bias, `h0`, ... for hidden nodes in sorted order and `o0`, ... for outputs.
+    Short,
+    /// Names given per id, falling back to the raw `u64` for ids without alias.
+    Aliased(HashMap<Id, String>),
+}
+
+/// Direction in which the network is laid out, see [`DotOptions::with_rank_direction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankDirection {
+    TopToBottom,
+    LeftToRight,
+}
+
+/// Configures the [DOT][1] output of [`Genome::dot_with_options`].
+///
+/// The default reproduces [`Genome::dot`].
+///
+/// # Examples
+///
+/// ```
+/// use set_genome::{DotOptions, Genome, NodeIds, Parameters, RankDirection};
+///
+/// let genome = Genome::initialized(&Parameters::basic(2, 1));
+///
+/// let options = DotOptions::default()
+///     .with_node_ids(NodeIds::Short)
+///     .with_weight_labels(true)
+///     .with_weight_colors(true)
+///     .with_rank_direction(RankDirection::LeftToRight);
+///
+/// let dot = Genome::dot_with_options(&genome, &options);
+///
+/// assert!(dot.contains("rankdir=LR"));
+/// assert!(dot.contains("\"i0\" -> \"o0\""));
+/// ```
+///
+/// [1]: https://www.graphviz.org/doc/info/lang.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotOptions {
+    node_ids: NodeIds,
+    weight_labels: bool,
+    weight_colors: bool,
+    hide_zero_weights: bool,
+    highlighted_nodes: HashSet<Id>,
+    highlighted_connections: HashSet<(Id, Id)>,
+    rank_direction: RankDirection,
+    rank_by_depth: bool,
+}
+
+impl Default for DotOptions {
+    fn default() -> Self {
+        Self {
+            node_ids: NodeIds::Raw,
+            weight_labels: false,
+            weight_colors: false,
+            hide_zero_weights: false,
+            highlighted_nodes: HashSet::new(),
+            highlighted_connections: HashSet::new(),
+            rank_direction: RankDirection::TopToBottom,
+            rank_by_depth: false,
+        }
+    }
+}
+
+const POSITIVE_WEIGHT_COLOR: &str = "#82B366";
+const NEGATIVE_WEIGHT_COLOR: &str = "#B85450";
+const HIGHLIGHT_COLOR: &str = "#E3008C";
+const RECURRENT_COLOR: &str = "#FF8000";
+
+impl DotOptions {
+    /// Names nodes by their raw id (default), a short positional name or a given alias.
+    pub fn with_node_ids(self, node_ids: NodeIds) -> Self {
+        Self { node_ids, ..self }
+    }
+
+    /// Prints the weight of every connection as its edge label.
+    pub fn with_weight_labels(self, weight_labels: bool) -> Self {
+        Self {
+            weight_labels,
+            ..self
+        }
+    }
+
+    /// Colors edges green for positive and red for negative weights.
+    /// Recurrent edges, like highlighted ones, are drawn as a pair of parallel lines in that color and their usual orange.
+    pub fn with_weight_colors(self, weight_colors: bool) -> Self {
+        Self {
+            weight_colors,
+            ..self
+        }
+    }
+
+    /// Leaves out connections with a weight of exactly zero.
+    pub fn with_zero_weights_hidden(self, hide_zero_weights: bool) -> Self {
+        Self {
+            hide_zero_weights,
+            ..self
+        }
+    }
+
+    /// Highlights the given nodes and connections, e.g. those changed by a mutation.
+    pub fn with_highlighted(
+        self,
+        nodes: impl IntoIterator<Item = Id>,
+        connections: impl IntoIterator<Item = (Id, Id)>,
+    ) -> Self {
+        Self {
+            highlighted_nodes: nodes.into_iter().collect(),
+            highlighted_connections: connections.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Lays out the network top to bottom (default) or left to right.
+    pub fn with_rank_direction(self, rank_direction: RankDirection) -> Self {
+        Self {
+            rank_direction,
+            ..self
+        }
+    }
+
+    /// Ranks hidden nodes by their depth, see [`Genome::layers`], instead of putting all of them on the same rank.
+    pub fn with_rank_by_depth(self, rank_by_depth: bool) -> Self {
+        Self {
+            rank_by_depth,
+            ..self
+        }
+    }
+
+    fn names(&self, genome: &Genome) -> HashMap<Id, String> {
+        match &self.node_ids {
+            NodeIds::Raw => genome
+                .nodes()
+                .map(|node| (node.id, node.id.0.to_string()))
+                .collect(),
+            NodeIds::Short => {
+                let mut names = HashMap::new();
+                for (prefix, nodes) in [
+                    ("i", genome.inputs.as_ordered_vec()),
+                    ("h", genome.hidden.as_ordered_vec()),
+                    ("o", genome.outputs.as_ordered_vec()),
+                ] {
+                    for (index, node) in nodes.into_iter().enumerate() {
+                        names.insert(node.id, quote(&format!("{}{}", prefix, index)));
+                    }
+                }
+                if let Some(bias) = &genome.bias {
+                    names.insert(bias.id, quote("b"));
+                }
+                names
+            }
+            NodeIds::Aliased(aliases) => genome
+                .nodes()
+                .map(|node| {
+                    let name = aliases
+                        .get(&node.id)
+                        .map(|alias| quote(alias))
+                        .unwrap_or_else(|| node.id.0.to_string());
+                    (node.id, name)
+                })
+                .collect(),
+        }
+    }
+
+    fn node(&self, names: &HashMap<Id, String>, node: &Node, kind: &str) -> String {
+        let name = &name(names, node.id);
+        let label = match self.node_ids {
+            NodeIds::Raw => kind.to_owned(),
+            // the name is already quoted, so strip its quotes to combine it with the kind
+            _ => format!("\"{}\\n{}\"", name.trim_matches('"'), kind),
+        };
+        let highlight = if self.highlighted_nodes.contains(&node.id) {
+            format!(" color=\"{}\" penwidth=3", HIGHLIGHT_COLOR)
+        } else {
+            String::new()
+        };
+        format!("\t\t{} [label={}{}];\n", name, label, highlight)
+    }
+
+    fn edge(
+        &self,
+        names: &HashMap<Id, String>,
+        connection: &Connection,
+        recurrent: bool,
+    ) -> Option<String> {
+        if self.hide_zero_weights && connection.weight == 0.0 {
+            return None;
+        }
+
+        let label = if self.weight_labels {
+            format!("{:.3}", connection.weight)
+        } else {
+            String::new()
+        };
+        let mut width = connection.weight.abs() * 0.95 + 0.05;
+        let mut attributes = String::new();
+        let color = if self.highlighted_connections.contains(&connection.id()) {
+            width = width.max(2.0);
+            Some(HIGHLIGHT_COLOR)
+        } else if self.weight_colors {
+            if connection.weight < 0.0 {
+                Some(NEGATIVE_WEIGHT_COLOR)
+            } else {
+                Some(POSITIVE_WEIGHT_COLOR)
+            }
+        } else {
+            None
+        };
+        match (color, recurrent) {
+            // a color list draws parallel lines, so recurrent edges stay recognizable
+            (Some(color), true) => {
+                attributes.push_str(&format!(" color=\"{}:{}\"", color, RECURRENT_COLOR))
+            }
+            (Some(color), false) => attributes.push_str(&format!(" color=\"{}\"", color)),
+            (None, _) => {}
+        }
+        if !connection.enabled {
+            attributes.push_str(" style=dashed");
+        }
+
+        Some(format!(
+            "\t\t{0} -> {1} [label={2:?} arrowsize={4:?} penwidth={4:?} tooltip={3:?} labeltooltip={3:?}{5}];\n",
+            name(names, connection.input),
+            name(names, connection.output),
+            label,
+            connection.weight,
+            width,
+            attributes
+        ))
+    }
+}
+
+/// Falls back to the raw id for nodes missing in the genome, so broken genomes can still be inspected.
+fn name(names: &HashMap<Id, String>, id: Id) -> String {
+    names.get(&id).cloned().unwrap_or_else(|| id.0.to_string())
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl Genome {
+    /// Get the encoded neural network as a string in [DOT][1] format.
+    ///
+    /// The output can be [visualized here][2] for example.
+    /// See [`Genome::dot_with_options`] to customize the output.
+    ///
+    /// [1]: https://www.graphviz.org/doc/info/lang.html
+    /// [2]: https://dreampuf.github.io/GraphvizOnline
+    pub fn dot(genome: &Self) -> String {
+        Self::dot_with_options(genome, &DotOptions::default())
+    }
+
+    /// Same as [`Genome::dot`] but configured by [`DotOptions`].
+    pub fn dot_with_options(genome: &Self, options: &DotOptions) -> String {
+        let names = options.names(genome);
+
+        let mut dot = "digraph {\n".to_owned();
+        match options.rank_direction {
+            RankDirection::TopToBottom => dot.push_str("\tgraph [splines=curved ranksep=8]\n"),
+            RankDirection::LeftToRight => {
+                dot.push_str("\tgraph [splines=curved ranksep=8 rankdir=LR]\n")
+            }
+        }
+
+        dot.push_str("\tsubgraph cluster_inputs {\n");
+        dot.push_str("\t\tgraph [label=\"Inputs\"]\n");
+        dot.push_str("\t\tnode [color=\"#D6B656\", fillcolor=\"#FFF2CC\", style=\"filled\"]\n");
+        dot.push('\n');
+        for node in genome.inputs.as_ordered_vec() {
+            // fill color: FFF2CC
+            // line color: D6B656
+
+            dot.push_str(&options.node(&names, node, &format!("{:?}", node.activation)));
+        }
+        if let Some(bias) = &genome.bias {
+            dot.push_str(&options.node(&names, bias, "Bias"));
+        }
+        dot.push_str("\t}\n");
+
+        dot.push_str("\tsubgraph hidden {\n");
+        if options.rank_by_depth {
+            dot.push_str("\t\tgraph [label=\"Hidden\"]\n");
+        } else {
+            dot.push_str("\t\tgraph [label=\"Hidden\" rank=\"same\"]\n");
+        }
+        dot.push_str("\t\tnode [color=\"#6C8EBF\", fillcolor=\"#DAE8FC\", style=\"filled\"]\n");
+        dot.push('\n');
+        for node in genome.hidden.as_ordered_vec() {
+            // fill color: DAE8FC
+            // line color: 6C8EBF
+
+            dot.push_str(&options.node(&names, node, &format!("{:?}", node.activation)));
+        }
+        dot.push_str("\t}\n");
+
+        dot.push_str("\tsubgraph cluster_outputs {\n");
+        dot.push_str("\t\tgraph [label=\"Outputs\" labelloc=\"b\"]\n");
+        dot.push_str("\t\tnode [color=\"#9673A6\", fillcolor=\"#E1D5E7\", style=\"filled\"]\n");
+        dot.push('\n');
+        for node in genome.outputs.as_ordered_vec() {
+            // fill color: E1D5E7
+            // line color: 9673A6
+
+            dot.push_str(&options.node(&names, node, &format!("{:?}", node.activation)));
+        }
+        dot.push_str("\t}\n");
+
+        if options.rank_by_depth {
+            let layers = genome.layers();
+            for (depth, layer) in layers.layers.iter().enumerate() {
+                let hidden = layer
+                    .iter()
+                    .filter(|&&id| genome.hidden.iter().any(|node| node.id == id))
+                    .map(|&id| format!("{};", name(&names, id)))
+                    .collect::<Vec<_>>();
+                if !hidden.is_empty() {
+                    dot.push_str(&format!(
+                        "\tsubgraph depth_{} {{\n\t\trank=\"same\"\n\t\t{}\n\t}}\n",
+                        depth,
+                        hidden.join(" ")
+                    ));
+                }
+            }
+        }
+
+        dot.push('\n');
+
+        dot.push_str("\tsubgraph feedforward_connections {\n");
+        dot.push('\n');
+        for connection in genome.feed_forward.as_sorted_vec() {
+            dot.extend(options.edge(&names, connection, false));
+        }
+        dot.push_str("\t}\n");
+
+        dot.push_str("\tsubgraph recurrent_connections {\n");
+        dot.push_str(&format!("\t\tedge [color=\"{}\"]\n", RECURRENT_COLOR));
+        dot.push('\n');
+        for connection in genome.recurrent.as_sorted_vec() {
+            // color: FF8000
+
+            dot.extend(options.edge(&names, connection, true));
+        }
+        dot.push_str("\t}\n");
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DotOptions, NodeIds, RankDirection, HIGHLIGHT_COLOR, NEGATIVE_WEIGHT_COLOR,
+        POSITIVE_WEIGHT_COLOR, RECURRENT_COLOR,
+    };
+    use crate::{
+        genes::{Activation, Connection, Genes, Id, Node},
+        BoundaryHandling, Genome, Mutations, Parameters, PerturbationDistribution, Structure,
+    };
+
+    #[test]
+    fn create_dot_from_genome() {
+        let genome = Genome {
+            inputs: Genes(vec![Node::input(Id(0), 0)].iter().cloned().collect()),
+            outputs: Genes(
+                vec![Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            hidden: Genes(
+                vec![Node::hidden(Id(2), Activation::Tanh)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                vec![
+                    Connection::new(Id(0), 0.25795942718883524, Id(2)),
+                    Connection::new(Id(2), -0.09736946507786626, Id(1)),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            recurrent: Genes(
+                vec![Connection::new(Id(1), 0.19777863112749228, Id(2))]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+
+        // let dot = "digraph {\n\t0 [label=Linear color=\"#D6B656\" fillcolor=\"#FFF2CC\" style=\"filled\"];\n\t2 [label=Tanh color=\"#6C8EBF\" fillcolor=\"#DAE8FC\" style=\"filled\"];\n\t1 [label=Linear color=\"#9673A6\" fillcolor=\"#E1D5E7\" style=\"filled\"];\n\t0 -> 2 [label=0.25795942718883524];\n\t2 -> 1 [label=0.09736946507786626];\n\t1 -> 2 [label=0.19777863112749228 color=\"#FF8000\"];\n}\n";
+
+        let dot = "digraph {
+\tgraph [splines=curved ranksep=8]
+\tsubgraph cluster_inputs {
+\t\tgraph [label=\"Inputs\"]
+\t\tnode [color=\"#D6B656\", fillcolor=\"#FFF2CC\", style=\"filled\"]
+
+\t\t0 [label=Linear];
+\t}
+\tsubgraph hidden {
+\t\tgraph [label=\"Hidden\" rank=\"same\"]
+\t\tnode [color=\"#6C8EBF\", fillcolor=\"#DAE8FC\", style=\"filled\"]
+
+\t\t2 [label=Tanh];
+\t}
+\tsubgraph cluster_outputs {
+\t\tgraph [label=\"Outputs\" labelloc=\"b\"]
+\t\tnode [color=\"#9673A6\", fillcolor=\"#E1D5E7\", style=\"filled\"]
+
+\t\t1 [label=Linear];
+\t}
+
+\tsubgraph feedforward_connections {
+
+\t\t0 -> 2 [label=\"\" arrowsize=0.29506145582939347 penwidth=0.29506145582939347 tooltip=0.25795942718883524 labeltooltip=0.25795942718883524];
+\t\t2 -> 1 [label=\"\" arrowsize=0.14250099182397294 penwidth=0.14250099182397294 tooltip=-0.09736946507786626 labeltooltip=-0.09736946507786626];
+\t}
+\tsubgraph recurrent_connections {
+\t\tedge [color=\"#FF8000\"]
+
+\t\t1 -> 2 [label=\"\" arrowsize=0.23788969957111766 penwidth=0.23788969957111766 tooltip=0.19777863112749228 labeltooltip=0.19777863112749228];
+\t}
+}
+";
+        assert_eq!(&Genome::dot(&genome), dot)
+    }
+
+    #[test]
+    fn rank_dot_by_depth() {
+        let genome = Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            outputs: Genes(
+                [Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            hidden: Genes(
+                [
+                    Node::hidden(Id(2), Activation::Tanh),
+                    Node::hidden(Id(3), Activation::Tanh),
+                    Node::hidden(Id(4), Activation::Tanh),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            feed_forward: Genes(
+                [
+                    Connection::new(Id(0), 1.0, Id(2)),
+                    Connection::new(Id(0), 1.0, Id(3)),
+                    Connection::new(Id(2), 1.0, Id(4)),
+                    Connection::new(Id(3), 1.0, Id(4)),
+                    Connection::new(Id(4), 1.0, Id(1)),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            ..Default::default()
+        };
+
+        let dot =
+            Genome::dot_with_options(&genome, &DotOptions::default().with_rank_by_depth(true));
+
+        assert!(dot.contains("\t\tgraph [label=\"Hidden\"]\n"));
+        assert!(dot.contains("\tsubgraph depth_1 {\n\t\trank=\"same\"\n\t\t2; 3;\n\t}\n"));
+        assert!(dot.contains("\tsubgraph depth_2 {\n\t\trank=\"same\"\n\t\t4;\n\t}\n"));
+        assert!(!Genome::dot(&genome).contains("subgraph depth_"));
+    }
+
+    #[test]
+    fn print_big_dot() {
+        let parameters = Parameters {
+            structure: Structure {
+                number_of_inputs: 10,
+                number_of_outputs: 10,
+                percent_of_connected_inputs: 0.2,
+                ..Default::default()
+            },
+            mutations: vec![
+                Mutations::ChangeWeights {
+                    chance: 1.0,
+                    percent_perturbed: 0.5,
+                    standard_deviation: 0.1,
+                    distribution: PerturbationDistribution::Normal,
+                    boundary_handling: BoundaryHandling::Reflect,
+                },
+                Mutations::ChangeActivation {
+                    chance: 0.05,
+                    activation_pool: vec![
+                        Activation::Linear,
+                        Activation::Sigmoid,
+                        Activation::Tanh,
+                        Activation::Gaussian,
+                        Activation::Step,
+                        Activation::Sine,
+                        Activation::Cosine,
+                        Activation::Inverse,
+                        Activation::Absolute,
+                        Activation::Relu,
+                    ],
+                },
+                Mutations::AddNode {
+                    chance: 0.005,
+                    activation_pool: vec![
+                        Activation::Linear,
+                        Activation::Sigmoid,
+                        Activation::Tanh,
+                        Activation::Gaussian,
+                        Activation::Step,
+                        Activation::Sine,
+                        Activation::Cosine,
+                        Activation::Inverse,
+                        Activation::Absolute,
+                        Activation::Relu,
+                    ],
+                },
+                Mutations::AddConnection { chance: 0.01 },
+                Mutations::AddRecurrentConnection { chance: 0.01 },
+            ],
+            ..Default::default()
+        };
+        let mut genome = Genome::initialized(&parameters);
+
+        for _ in 0..1000 {
+            let _ = genome.mutate(&parameters);
+        }
+
+        print!("{}", Genome::dot(&genome));
+    }
+
+    fn small_genome() -> Genome {
+        Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            bias: Some(Node::bias(Id(5))),
+            outputs: Genes(
+                [Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            hidden: Genes(
+                [Node::hidden(Id(2), Activation::Tanh)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [
+                    Connection::new(Id(0), 0.5, Id(2)),
+                    Connection::new(Id(2), -0.25, Id(1)),
+                    Connection::new(Id(5), 0.0, Id(1)),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn short_and_aliased_ids() {
+        let genome = small_genome();
+
+        let dot = Genome::dot_with_options(
+            &genome,
+            &DotOptions::default().with_node_ids(NodeIds::Short),
+        );
+        assert!(dot.contains("\t\t\"i0\" [label=\"i0\\nLinear\"];\n"));
+        assert!(dot.contains("\t\t\"b\" [label=\"b\\nBias\"];\n"));
+        assert!(dot.contains("\t\t\"h0\" -> \"o0\""));
+
+        let dot = Genome::dot_with_options(
+            &genome,
+            &DotOptions::default().with_node_ids(NodeIds::Aliased(
+                [(Id(0), "x".to_owned())].iter().cloned().collect(),
+            )),
+        );
+        assert!(dot.contains("\t\t\"x\" -> 2 "));
+    }
+
+    #[test]
+    fn style_edges_by_weight() {
+        let genome = small_genome();
+
+        let dot = Genome::dot_with_options(
+            &genome,
+            &DotOptions::default()
+                .with_weight_labels(true)
+                .with_weight_colors(true)
+                .with_zero_weights_hidden(true),
+        );
+
+        assert!(dot.contains("0 -> 2 [label=\"0.500\""));
+        assert!(dot.contains(&format!("color=\"{}\"", POSITIVE_WEIGHT_COLOR)));
+        assert!(dot.contains(&format!("color=\"{}\"", NEGATIVE_WEIGHT_COLOR)));
+        assert!(!dot.contains("5 -> 1"));
+    }
+
+    #[test]
+    fn highlight_genes_and_layout() {
+        let genome = small_genome();
+
+        let dot = Genome::dot_with_options(
+            &genome,
+            &DotOptions::default()
+                .with_highlighted(vec![Id(2)], vec![(Id(2), Id(1))])
+                .with_rank_direction(RankDirection::LeftToRight),
+        );
+
+        assert!(dot.contains("rankdir=LR"));
+        assert!(dot.contains(&format!(
+            "\t\t2 [label=Tanh color=\"{}\" penwidth=3];\n",
+            HIGHLIGHT_COLOR
+        )));
+        assert!(dot.contains(&format!(
+            "2 -> 1 [label=\"\" arrowsize=2.0 penwidth=2.0 tooltip=-0.25 labeltooltip=-0.25 color=\"{}\"];",
+            HIGHLIGHT_COLOR
+        )));
+    }
+
+    #[test]
+    fn keep_recurrent_edges_distinct() {
+        let mut genome = small_genome();
+        genome.recurrent.insert(Connection::new(Id(1), -0.5, Id(2)));
+
+        let dot = Genome::dot_with_options(
+            &genome,
+            &DotOptions::default()
+                .with_weight_colors(true)
+                .with_highlighted(vec![], vec![(Id(1), Id(2))]),
+        );
+
+        assert!(dot.contains(&format!(
+            "1 -> 2 [label=\"\" arrowsize=2.0 penwidth=2.0 tooltip=-0.5 labeltooltip=-0.5 color=\"{}:{}\"];",
+            HIGHLIGHT_COLOR, RECURRENT_COLOR
+        )));
+        assert!(dot.contains(&format!("color=\"{}\"", NEGATIVE_WEIGHT_COLOR)));
+    }
+
+    #[test]
+    fn print_connections_to_unknown_nodes() {
+        let mut genome = small_genome();
+        genome
+            .feed_forward
+            .insert(Connection::new(Id(2), 1.0, Id(42)));
+
+        let dot = Genome::dot(&genome);
+        assert!(dot.contains("\t\t2 -> 42 "));
+
+        let dot = Genome::dot_with_options(
+            &genome,
+            &DotOptions::default().with_node_ids(NodeIds::Short),
+        );
+        assert!(dot.contains("\t\t\"h0\" -> 42 "));
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    genes::{Connection, Genes, Id},
+    Genome,
+};
+
+/// Configures which connections [`Genome::prune`] considers dead.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct PruneOptions {
+    /// Connections with an absolute weight of at most this value are removed, zero only removes exactly zero weights.
+    pub weight_threshold: f64,
+    /// Removes disabled connections, e.g. those split by [`crate::Mutations::add_node`].
+    pub remove_disabled: bool,
+}
+
+impl Default for PruneOptions {
+    fn default() -> Self {
+        Self {
+            weight_threshold: 0.0,
+            remove_disabled: true,
+        }
+    }
+}
+
+/// Lists everything removed by [`Genome::prune`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruneSummary {
+    pub removed_nodes: Vec<Id>,
+    pub removed_feed_forward: Vec<(Id, Id)>,
+    pub removed_recurrent: Vec<(Id, Id)>,
+}
+
+impl PruneSummary {
+    /// Is true when nothing was removed.
+    pub fn is_empty(&self) -> bool {
+        self.removed_nodes.is_empty()
+            && self.removed_feed_forward.is_empty()
+            && self.removed_recurrent.is_empty()
+    }
+}
+
+impl Genome {
+    /// Removes structure that does not contribute to the outputs.
+    ///
+    /// First all dead connections according to `options` are removed.
+    /// Then every hidden node that can not be reached from any input or bias node, or can not reach any output node, is removed together with its connections.
+    /// Connections referencing nodes not present in the genome are removed as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use set_genome::{Genome, Mutations, Parameters, PruneOptions, activations::Activation};
+    ///
+    /// let mut genome = Genome::initialized(&Parameters::basic(2, 1));
+    ///
+    /// Mutations::add_node(&[Activation::Tanh], &mut genome, &mut rand::thread_rng()).unwrap();
+    ///
+    /// // the connection split by the new node is disabled
+    /// let summary = genome.prune(&PruneOptions::default());
+    ///
+    /// assert_eq!(summary.removed_feed_forward.len(), 1);
+    /// assert!(summary.removed_nodes.is_empty());
+    /// ```
+    pub fn prune(&mut self, options: &PruneOptions) -> PruneSummary {
+        let dead = |connection: &Connection| {
+            connection.weight.abs() <= options.weight_threshold
+                || (options.remove_disabled && !connection.enabled)
+        };
+
+        let mut removed_feed_forward = Self::remove_connections(&mut self.feed_forward, dead);
+        let mut removed_recurrent = Self::remove_connections(&mut self.recurrent, dead);
+
+        // walk the connections from the inputs forwards and from the outputs backwards
+        let reachable = |starts: Vec<Id>, edges: &HashMap<Id, Vec<Id>>| {
+            let mut visited = HashSet::new();
+            let mut to_visit = starts;
+            while let Some(node) = to_visit.pop() {
+                if visited.insert(node) {
+                    to_visit.extend(edges.get(&node).into_iter().flatten());
+                }
+            }
+            visited
+        };
+
+        let mut forward: HashMap<Id, Vec<Id>> = HashMap::new();
+        let mut backward: HashMap<Id, Vec<Id>> = HashMap::new();
+        for connection in self.connections() {
+            forward
+                .entry(connection.input)
+                .or_default()
+                .push(connection.output);
+            backward
+                .entry(connection.output)
+                .or_default()
+                .push(connection.input);
+        }
+
+        let from_inputs = reachable(
+            self.inputs
+                .iter()
+                .chain(self.bias.iter())
+                .map(|node| node.id)
+                .collect(),
+            &forward,
+        );
+        let to_outputs = reachable(self.outputs.iter().map(|node| node.id).collect(), &backward);
+
+        let mut removed_nodes = self
+            .hidden
+            .iter()
+            .filter(|node| !(from_inputs.contains(&node.id) && to_outputs.contains(&node.id)))
+            .map(|node| node.id)
+            .collect::<Vec<_>>();
+        removed_nodes.sort_unstable();
+
+        self.hidden.retain(|node| !removed_nodes.contains(&node.id));
+
+        let nodes = self.nodes().map(|node| node.id).collect::<HashSet<_>>();
+        let orphaned = |connection: &Connection| {
+            !nodes.contains(&connection.input) || !nodes.contains(&connection.output)
+        };
+
+        removed_feed_forward.extend(Self::remove_connections(&mut self.feed_forward, orphaned));
+        removed_recurrent.extend(Self::remove_connections(&mut self.recurrent, orphaned));
+        removed_feed_forward.sort_unstable();
+        removed_recurrent.sort_unstable();
+
+        self.order_hidden_nodes();
+
+        PruneSummary {
+            removed_nodes,
+            removed_feed_forward,
+            removed_recurrent,
+        }
+    }
+
+    fn remove_connections(
+        connections: &mut Genes<Connection>,
+        remove: impl Fn(&Connection) -> bool,
+    ) -> Vec<(Id, Id)> {
+        let removed = connections
+            .iter()
+            .filter(|connection| remove(connection))
+            .map(|connection| connection.id())
+            .collect::<Vec<_>>();
+        connections.retain(|connection| !remove(connection));
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        activations::Activation,
+        genes::{Connection, Genes, Id, Node},
+        Genome, PruneOptions,
+    };
+
+    #[test]
+    fn remove_unreachable_hidden_nodes() {
+        let mut genome = Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            hidden: Genes(
+                [
+                    Node::hidden(Id(2), Activation::Linear),
+                    Node::hidden(Id(3), Activation::Linear),
+                    Node::hidden(Id(4), Activation::Linear),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            outputs: Genes(
+                [Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [
+                    Connection::new(Id(0), 1.0, Id(2)),
+                    Connection::new(Id(2), 1.0, Id(1)),
+                    // 3 can not reach an output
+                    Connection::new(Id(0), 1.0, Id(3)),
+                    // 4 is not reachable from an input
+                    Connection::new(Id(4), 1.0, Id(1)),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            recurrent: Genes(
+                [
+                    Connection::new(Id(3), 1.0, Id(3)),
+                    Connection::new(Id(1), 1.0, Id(2)),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            ..Default::default()
+        };
+
+        let summary = genome.prune(&PruneOptions::default());
+
+        assert_eq!(summary.removed_nodes, vec![Id(3), Id(4)]);
+        assert_eq!(
+            summary.removed_feed_forward,
+            vec![(Id(0), Id(3)), (Id(4), Id(1))]
+        );
+        assert_eq!(summary.removed_recurrent, vec![(Id(3), Id(3))]);
+        assert_eq!(genome.hidden.len(), 1);
+        assert_eq!(genome.recurrent.len(), 1);
+        assert_eq!(genome.validate(), Ok(()));
+    }
+
+    #[test]
+    fn remove_weak_connections() {
+        let mut genome = Genome {
+            inputs: Genes(
+                [Node::input(Id(0), 0), Node::input(Id(1), 1)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            hidden: Genes(
+                [Node::hidden(Id(3), Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            outputs: Genes(
+                [Node::output(Id(2), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [
+                    Connection::new(Id(0), 0.5, Id(2)),
+                    Connection::new(Id(1), 0.0, Id(2)),
+                    Connection::new(Id(1), 0.05, Id(3)),
+                    Connection::new(Id(3), 0.5, Id(2)),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            ..Default::default()
+        };
+
+        let mut zero = genome.clone();
+        let summary = zero.prune(&PruneOptions::default());
+        assert_eq!(summary.removed_feed_forward, vec![(Id(1), Id(2))]);
+
+        // removing the only input of the hidden node removes it as well
+        let summary = genome.prune(&PruneOptions {
+            weight_threshold: 0.1,
+            ..Default::default()
+        });
+        assert_eq!(summary.removed_nodes, vec![Id(3)]);
+        assert_eq!(genome.feed_forward.len(), 1);
+        assert!(genome.prune(&PruneOptions::default()).is_empty());
+    }
+}
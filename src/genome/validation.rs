@@ -0,0 +1,306 @@
+use std::collections::{HashMap, HashSet};
+
+use thiserror::Error;
+
+use crate::{
+    genes::{Connection, Id},
+    Genome,
+};
+
+/// A broken invariant of a [`Genome`], see [`Genome::validate`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum GenomeViolation {
+    #[error("The id {0:?} is used by more than one node.")]
+    DuplicateNodeId(Id),
+    #[error("More than one input node has the order {0}.")]
+    DuplicateInputOrder(usize),
+    #[error("More than one output node has the order {0}.")]
+    DuplicateOutputOrder(usize),
+    #[error("The connection {connection:?} references the node {node:?} which is not part of the genome.")]
+    UnknownNode { connection: (Id, Id), node: Id },
+    #[error("The feed-forward connection {0:?} ends in an input or bias node.")]
+    FeedForwardIntoInput((Id, Id)),
+    #[error("The feed-forward connection {0:?} starts at an output node.")]
+    FeedForwardFromOutput((Id, Id)),
+    #[error("The recurrent connection {0:?} ends in an input or bias node.")]
+    RecurrentIntoInput((Id, Id)),
+    #[error("The feed-forward connections contain a cycle.")]
+    FeedForwardCycle,
+    #[error("The hidden node {0:?} lacks incoming or outgoing connections.")]
+    DanglingHiddenNode(Id),
+}
+
+impl Genome {
+    /// Checks every invariant the mutations rely on and lists all violations found.
+    ///
+    /// Genomes created and altered by this crate are always valid, this is meant for genomes from other sources, e.g. deserialized ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use set_genome::{Genome, GenomeViolation, Id, Parameters};
+    ///
+    /// let mut genome = Genome::initialized(&Parameters::basic(2, 1));
+    ///
+    /// assert_eq!(genome.validate(), Ok(()));
+    ///
+    /// let mut connection = genome.feed_forward.iter().next().cloned().unwrap();
+    /// let input = connection.input;
+    /// connection.output = Id(42);
+    /// genome.feed_forward.insert(connection);
+    ///
+    /// assert_eq!(
+    ///     genome.validate().unwrap_err()[0],
+    ///     GenomeViolation::UnknownNode {
+    ///         connection: (input, Id(42)),
+    ///         node: Id(42)
+    ///     }
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<GenomeViolation>> {
+        let mut violations = Vec::new();
+
+        let mut ids = HashSet::new();
+        for node in self.nodes() {
+            if !ids.insert(node.id) {
+                violations.push(GenomeViolation::DuplicateNodeId(node.id));
+            }
+        }
+
+        let mut orders = HashSet::new();
+        for node in self.inputs.as_ordered_vec() {
+            if !orders.insert(node.order) {
+                violations.push(GenomeViolation::DuplicateInputOrder(node.order));
+            }
+        }
+
+        orders.clear();
+        for node in self.outputs.as_ordered_vec() {
+            if !orders.insert(node.order) {
+                violations.push(GenomeViolation::DuplicateOutputOrder(node.order));
+            }
+        }
+
+        let is_input = |id: Id| {
+            self.inputs.iter().any(|node| node.id == id)
+                || self.bias.iter().any(|node| node.id == id)
+        };
+        let is_output = |id: Id| self.outputs.iter().any(|node| node.id == id);
+
+        let mut known = |connection: &Connection| {
+            let mut known = true;
+            for node in [connection.input, connection.output] {
+                if !ids.contains(&node) {
+                    violations.push(GenomeViolation::UnknownNode {
+                        connection: connection.id(),
+                        node,
+                    });
+                    known = false;
+                }
+            }
+            known
+        };
+
+        let feed_forward = self
+            .feed_forward
+            .as_sorted_vec()
+            .into_iter()
+            .filter(|connection| known(connection))
+            .collect::<Vec<_>>();
+        let recurrent = self
+            .recurrent
+            .as_sorted_vec()
+            .into_iter()
+            .filter(|connection| known(connection))
+            .collect::<Vec<_>>();
+
+        for connection in &feed_forward {
+            if is_input(connection.output) {
+                violations.push(GenomeViolation::FeedForwardIntoInput(connection.id()));
+            }
+            if is_output(connection.input) {
+                violations.push(GenomeViolation::FeedForwardFromOutput(connection.id()));
+            }
+        }
+
+        for connection in &recurrent {
+            if is_input(connection.output) {
+                violations.push(GenomeViolation::RecurrentIntoInput(connection.id()));
+            }
+        }
+
+        // Kahn's algorithm, nodes that never become free of dependencies are part of or behind a cycle
+        let mut pending: HashMap<Id, usize> = HashMap::new();
+        let mut dependents: HashMap<Id, Vec<Id>> = HashMap::new();
+        for connection in &feed_forward {
+            *pending.entry(connection.output).or_default() += 1;
+            dependents
+                .entry(connection.input)
+                .or_default()
+                .push(connection.output);
+        }
+        let mut ready = ids
+            .iter()
+            .filter(|id| !pending.contains_key(id))
+            .copied()
+            .collect::<Vec<_>>();
+        let mut resolved = 0;
+
+        while let Some(node) = ready.pop() {
+            resolved += 1;
+            for &dependent in dependents.get(&node).into_iter().flatten() {
+                let count = pending.get_mut(&dependent).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(dependent);
+                }
+            }
+        }
+
+        if resolved < ids.len() {
+            violations.push(GenomeViolation::FeedForwardCycle);
+        }
+
+        for node in self.hidden.iter() {
+            if !self.connections().any(|c| c.output == node.id)
+                || !self.connections().any(|c| c.input == node.id)
+            {
+                violations.push(GenomeViolation::DanglingHiddenNode(node.id));
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use crate::{
+        activations::Activation,
+        genes::{Connection, Genes, Id, Node},
+        Genome, GenomeViolation, Mutations, Parameters,
+    };
+
+    fn small_genome() -> Genome {
+        Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            hidden: Genes(
+                [Node::hidden(Id(2), Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            outputs: Genes(
+                [Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [
+                    Connection::new(Id(0), 1.0, Id(2)),
+                    Connection::new(Id(2), 1.0, Id(1)),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn valid_genomes() {
+        assert_eq!(small_genome().validate(), Ok(()));
+        assert_eq!(Genome::default().validate(), Ok(()));
+
+        let parameters = Parameters::default();
+        let mut genome = Genome::initialized(&parameters);
+        for _ in 0..100 {
+            let _ = genome.mutate(&parameters);
+        }
+        assert_eq!(genome.validate(), Ok(()));
+    }
+
+    #[test]
+    fn mutate_invalid_genomes() {
+        let mut genome = small_genome();
+        genome.recurrent.insert(Connection::new(Id(2), 1.0, Id(42)));
+
+        // debug builds only check genomes that were valid before the mutation
+        Mutations::AddConnection { chance: 1.0 }
+            .apply(&mut genome, &mut thread_rng())
+            .unwrap();
+
+        assert!(genome.validate().is_err());
+    }
+
+    #[test]
+    fn cross_invalid_genomes() {
+        let genome = small_genome();
+        let mut invalid = small_genome();
+        invalid.inputs.insert(Node::input(Id(3), 0));
+
+        // debug builds only check offspring of valid parents
+        let offspring = invalid.cross_in(&genome);
+
+        assert!(offspring.validate().is_err());
+    }
+
+    #[test]
+    fn detect_duplicates() {
+        let mut genome = small_genome();
+        genome.inputs.insert(Node::input(Id(3), 0));
+        genome
+            .outputs
+            .insert(Node::output(Id(2), 1, Activation::Linear));
+
+        let violations = genome.validate().unwrap_err();
+
+        assert!(violations.contains(&GenomeViolation::DuplicateNodeId(Id(2))));
+        assert!(violations.contains(&GenomeViolation::DuplicateInputOrder(0)));
+    }
+
+    #[test]
+    fn detect_invalid_connections() {
+        let mut genome = small_genome();
+        genome
+            .feed_forward
+            .insert(Connection::new(Id(1), 1.0, Id(0)));
+        genome.recurrent.insert(Connection::new(Id(2), 1.0, Id(0)));
+        genome.recurrent.insert(Connection::new(Id(2), 1.0, Id(42)));
+
+        assert_eq!(
+            genome.validate(),
+            Err(vec![
+                GenomeViolation::UnknownNode {
+                    connection: (Id(2), Id(42)),
+                    node: Id(42)
+                },
+                GenomeViolation::FeedForwardIntoInput((Id(1), Id(0))),
+                GenomeViolation::FeedForwardFromOutput((Id(1), Id(0))),
+                GenomeViolation::RecurrentIntoInput((Id(2), Id(0))),
+                GenomeViolation::FeedForwardCycle,
+            ])
+        );
+    }
+
+    #[test]
+    fn detect_dangling_hidden_node() {
+        let mut genome = small_genome();
+        genome
+            .feed_forward
+            .remove(&Connection::new(Id(2), 1.0, Id(1)));
+
+        assert_eq!(
+            genome.validate(),
+            Err(vec![GenomeViolation::DanglingHiddenNode(Id(2))])
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! Tracks which structural event produced which node [`Id`] across many genomes.
+//!
+//! New node ids are hashes of the gene they originate from and a counter on that gene, see [`crate::Connection::next_id`] and [`crate::Node::next_id`].
+//! When the computed id is already taken the counter is advanced, so the same event can produce different ids in different genomes.
+//! The [`IdRegistry`] recovers the origin of every hidden node from the genes and counters present in a genome and makes such divergences visible.
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{genes::Id, Genome};
+
+/// A mutation that introduces a new node id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StructuralEvent {
+    /// [`crate::Mutations::add_node`] split this feed-forward connection.
+    SplitConnection { connection: (Id, Id) },
+    /// [`crate::Mutations::duplicate_node`] duplicated this hidden node.
+    DuplicateNode { node: Id },
+}
+
+/// The event that produced an id, `attempt` counts the ids the event produced before, e.g. because they collided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Innovation {
+    pub event: StructuralEvent,
+    pub attempt: u64,
+}
+
+/// An event that produced different ids in different genomes, see [`IdRegistry::divergences`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Divergence {
+    pub event: StructuralEvent,
+    /// The ids carried by observed genomes, sorted by the attempt that produced them.
+    pub ids: Vec<Id>,
+}
+
+/// Records the origin of node ids across a population.
+///
+/// Genomes are identified by a key chosen by the caller, e.g. their index into a population, see `Population::register_ids`.
+/// Origins are kept once known, so they survive the removal of the gene they originate from in later generations.
+///
+/// # Examples
+///
+/// ```
+/// use set_genome::{Genome, IdRegistry, Mutations, Parameters, activations::Activation};
+///
+/// let parent = Genome::initialized(&Parameters::basic(1, 1));
+/// let mut child = parent.clone();
+///
+/// Mutations::add_node(&[Activation::Tanh], &mut child, &mut rand::thread_rng()).unwrap();
+///
+/// let mut registry = IdRegistry::default();
+/// registry.observe(0, &parent);
+/// assert!(registry.observe(1, &child).is_empty());
+///
+/// let node = child.hidden.iter().next().unwrap().id;
+///
+/// assert!(registry.origin(node).is_some());
+/// assert_eq!(registry.sharing_innovation(node).into_iter().collect::<Vec<_>>(), vec![1]);
+/// assert!(registry.divergences().is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IdRegistry {
+    origins: HashMap<Id, Innovation>,
+    ids: HashMap<StructuralEvent, BTreeSet<(u64, Id)>>,
+    carriers: HashMap<Id, BTreeSet<usize>>,
+}
+
+impl IdRegistry {
+    /// Registers that `id` was produced by `innovation` and is carried by `genome`.
+    pub fn record(&mut self, genome: usize, id: Id, innovation: Innovation) {
+        self.origins.insert(id, innovation);
+        self.ids
+            .entry(innovation.event)
+            .or_default()
+            .insert((innovation.attempt, id));
+        self.carriers.entry(id).or_default().insert(genome);
+    }
+
+    /// Registers all hidden nodes of `genome` under the key `key` and resolves their origin.
+    ///
+    /// Returns the ids whose origin is neither derivable from `genome` nor known from earlier observations, sorted.
+    pub fn observe(&mut self, key: usize, genome: &Genome) -> Vec<Id> {
+        let innovations = Self::innovations(genome);
+        let mut unknown = Vec::new();
+
+        for node in genome.hidden.iter() {
+            if let Some(&innovation) = innovations.get(&node.id) {
+                self.record(key, node.id, innovation);
+            } else {
+                self.carriers.entry(node.id).or_default().insert(key);
+                if !self.origins.contains_key(&node.id) {
+                    unknown.push(node.id);
+                }
+            }
+        }
+
+        unknown.sort_unstable();
+        unknown
+    }
+
+    /// Forgets which genomes carry which ids while keeping all known origins, e.g. before observing the next generation.
+    pub fn clear_carriers(&mut self) {
+        self.carriers.clear();
+    }
+
+    /// The innovation that produced `id`, if known.
+    pub fn origin(&self, id: Id) -> Option<Innovation> {
+        self.origins.get(&id).copied()
+    }
+
+    /// All ids known to originate from `event`, sorted by attempt.
+    pub fn ids_of(&self, event: StructuralEvent) -> impl Iterator<Item = Id> + '_ {
+        self.ids
+            .get(&event)
+            .into_iter()
+            .flatten()
+            .map(|&(_, id)| id)
+    }
+
+    /// Keys of all observed genomes that carry a node with `id`.
+    pub fn carriers(&self, id: Id) -> impl Iterator<Item = usize> + '_ {
+        self.carriers.get(&id).into_iter().flatten().copied()
+    }
+
+    /// Keys of all observed genomes that carry a node produced by the same event as `node`, regardless of the id the event produced there.
+    pub fn sharing_innovation(&self, node: Id) -> BTreeSet<usize> {
+        match self.origin(node) {
+            Some(innovation) => self
+                .ids_of(innovation.event)
+                .flat_map(|id| self.carriers(id))
+                .collect(),
+            None => self.carriers(node).collect(),
+        }
+    }
+
+    /// All events that produced different ids in different genomes, sorted by event.
+    ///
+    /// An event diverges when two of its ids are carried by disjoint sets of genomes.
+    /// A genome that repeats an event, e.g. splits the same connection twice, carries several of its ids without them diverging.
+    pub fn divergences(&self) -> Vec<Divergence> {
+        let mut divergences = self
+            .ids
+            .iter()
+            .filter_map(|(&event, ids)| {
+                let carried = ids
+                    .iter()
+                    .filter_map(|(_, id)| self.carriers.get(id).map(|carriers| (*id, carriers)))
+                    .filter(|(_, carriers)| !carriers.is_empty())
+                    .collect::<Vec<_>>();
+
+                let diverged = carried.iter().enumerate().any(|(index, (_, first))| {
+                    carried[index + 1..]
+                        .iter()
+                        .any(|(_, second)| first.is_disjoint(second))
+                });
+
+                if diverged {
+                    Some(Divergence {
+                        event,
+                        ids: carried.into_iter().map(|(id, _)| id).collect(),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        divergences.sort_unstable_by_key(|divergence| divergence.event);
+        divergences
+    }
+
+    /// Replays the id counters of all genes that can produce node ids.
+    fn innovations(genome: &Genome) -> HashMap<Id, Innovation> {
+        let mut innovations = HashMap::new();
+
+        for connection in genome.feed_forward.iter() {
+            let mut replay = connection.clone();
+            replay.id_counter = 0;
+            for attempt in 0..connection.id_counter {
+                innovations.insert(
+                    replay.next_id(),
+                    Innovation {
+                        event: StructuralEvent::SplitConnection {
+                            connection: connection.id(),
+                        },
+                        attempt,
+                    },
+                );
+            }
+        }
+
+        for node in genome.hidden.iter() {
+            let mut replay = node.clone();
+            replay.id_counter = 0;
+            for attempt in 0..node.id_counter {
+                innovations.insert(
+                    replay.next_id(),
+                    Innovation {
+                        event: StructuralEvent::DuplicateNode { node: node.id },
+                        attempt,
+                    },
+                );
+            }
+        }
+
+        innovations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use crate::{activations::Activation, Connection, Genome, Mutations, Node, Parameters};
+
+    use super::{Divergence, IdRegistry, Innovation, StructuralEvent};
+
+    #[test]
+    fn resolve_split_and_duplication() {
+        let mut rng = SmallRng::seed_from_u64(42);
+        let mut genome = Genome::initialized(&Parameters::basic(1, 1));
+        let connection = genome.feed_forward.iter().next().unwrap().id();
+
+        Mutations::add_node(&[Activation::Tanh], &mut genome, &mut rng).unwrap();
+        let split = genome.hidden.iter().next().unwrap().id;
+        Mutations::duplicate_node(&mut genome, &mut rng).unwrap();
+
+        let mut registry = IdRegistry::default();
+        assert!(registry.observe(0, &genome).is_empty());
+
+        assert_eq!(
+            registry.origin(split),
+            Some(Innovation {
+                event: StructuralEvent::SplitConnection { connection },
+                attempt: 0,
+            })
+        );
+
+        let duplicate = genome
+            .hidden
+            .iter()
+            .find(|node| node.id != split)
+            .unwrap()
+            .id;
+        assert_eq!(
+            registry.origin(duplicate),
+            Some(Innovation {
+                event: StructuralEvent::DuplicateNode { node: split },
+                attempt: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn detect_divergent_ids() {
+        let mut rng = SmallRng::seed_from_u64(42);
+        let parent = Genome::initialized(&Parameters::basic(1, 1));
+        let connection = parent.feed_forward.iter().next().unwrap().clone();
+
+        let mut first = parent.clone();
+        Mutations::add_node(&[Activation::Tanh], &mut first, &mut rng).unwrap();
+
+        // as if the first id had collided in the lineage of the second genome
+        let mut second = parent.clone();
+        second.feed_forward.replace(Connection {
+            id_counter: 1,
+            ..connection.clone()
+        });
+        Mutations::add_node(&[Activation::Tanh], &mut second, &mut rng).unwrap();
+
+        let first_id = first.hidden.iter().next().unwrap().id;
+        let second_id = second.hidden.iter().next().unwrap().id;
+        assert_ne!(first_id, second_id);
+
+        let mut registry = IdRegistry::default();
+        registry.observe(0, &first);
+        registry.observe(1, &second);
+        registry.observe(2, &parent);
+
+        let event = StructuralEvent::SplitConnection {
+            connection: connection.id(),
+        };
+        assert_eq!(
+            registry.divergences(),
+            vec![Divergence {
+                event,
+                ids: vec![first_id, second_id],
+            }]
+        );
+        assert_eq!(registry.carriers(first_id).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(
+            registry
+                .sharing_innovation(first_id)
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+
+        // origins outlive the genes they were derived from
+        registry.clear_carriers();
+        let mut pruned = second.clone();
+        pruned.prune(&Default::default());
+        assert!(registry.observe(3, &pruned).is_empty());
+        assert_eq!(registry.sharing_innovation(first_id).len(), 1);
+    }
+
+    #[test]
+    fn repeated_event_in_one_genome_does_not_diverge() {
+        let parent = Genome::initialized(&Parameters::basic(1, 1));
+        let connection = parent.feed_forward.iter().next().unwrap().clone();
+
+        // the connection was split twice in the same lineage and got re-enabled in between
+        let mut genome = parent.clone();
+        let mut replay = Connection {
+            id_counter: 0,
+            ..connection.clone()
+        };
+        let first_id = replay.next_id();
+        let second_id = replay.next_id();
+        genome.feed_forward.replace(replay);
+        genome
+            .hidden
+            .insert(Node::hidden(first_id, Activation::Tanh));
+        genome
+            .hidden
+            .insert(Node::hidden(second_id, Activation::Tanh));
+
+        let mut registry = IdRegistry::default();
+        assert!(registry.observe(0, &genome).is_empty());
+        assert!(registry.observe(1, &genome).is_empty());
+
+        assert_eq!(
+            registry
+                .ids_of(StructuralEvent::SplitConnection {
+                    connection: connection.id()
+                })
+                .collect::<Vec<_>>(),
+            vec![first_id, second_id]
+        );
+        assert!(registry.divergences().is_empty());
+    }
+}
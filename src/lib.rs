@@ -6,7 +6,6 @@
 //! # use favannat::{
 //! #   MatrixFeedforwardFabricator, Evaluator, Fabricator,
 //! # };
-//! # use nalgebra::dmatrix;
 //! // Setup a genome context for networks with 10 inputs and 10 outputs.
 //! let parameters = Parameters::basic(10, 10);
 //!
@@ -17,10 +16,10 @@
 //! genome.mutate(&parameters);
 //!
 //! // Get a phenotype of the genome.
-//! let network = MatrixFeedforwardFabricator::fabricate(&genome).expect("Cool network.");
+//! let network = MatrixFeedforwardFabricator::fabricate(&genome.favannat_net()).expect("Cool network.");
 //!
 //! // Evaluate a network on an input.
-//! let output = network.evaluate(dmatrix![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+//! let output = network.evaluate(vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
 //! ```
 //!
 //! # SET genome
@@ -53,9 +52,12 @@
 //!         // specified output activation
 //!         outputs_activation: Activation::Tanh,
 //!         // seed for initial genome construction
-//!         seed: 42
+//!         seed: 42,
+//!         // no bias node
+//!         bias: false,
 //!     },
 //!     mutations: vec![],
+//!     ..Default::default()
 //! };
 //! ```
 //! This allows us to create an initialized genome which conforms to our description above:
@@ -74,9 +76,12 @@
 //! #         // specified output activation
 //! #         outputs_activation: Activation::Tanh,
 //!           // seed for initial genome construction
-//!           seed: 42
+//!           seed: 42,
+//! #         // no bias node
+//! #         bias: false,
 //! #     },
 //! #     mutations: vec![],
+//! #     ..Default::default()
 //! # };
 //! #
 //! let genome_with_connections = Genome::initialized(&parameters);
@@ -98,10 +103,12 @@
 //! #         // specified output activation
 //! #         outputs_activation: Activation::Tanh,
 //!           // seed for initial genome construction
-//!           seed: 42
-//!
+//!           seed: 42,
+//! #         // no bias node
+//! #         bias: false,
 //! #     },
 //! #     mutations: vec![],
+//! #     ..Default::default()
 //! # };
 //! #
 //! let genome_without_connections = Genome::uninitialized(&parameters);
@@ -117,10 +124,15 @@
 //! - [`Mutations::add_node`]
 //! - [`Mutations::add_recurrent_connection`]
 //! - [`Mutations::change_activation`]
+//! - [`Mutations::change_node_parameters`]
 //! - [`Mutations::change_weights`]
 //! - [`Mutations::remove_node`]
 //! - [`Mutations::remove_connection`]
 //! - [`Mutations::remove_recurrent_connection`]
+//! - [`Mutations::replace_weights`]
+//! - [`Mutations::prune`]
+//! - [`Mutations::toggle_connection`]
+//! - [`Mutations::re_enable_connection`]
 //!
 //! //! # Features
 //!
@@ -133,9 +145,19 @@
 //! set-genome = { version = "x.x.x", default-features = false }
 //! ```
 //!
-//! If you are interested how they connect, [see here].
+//! The genome is handed to favannat via [`Genome::favannat_net`], if you are interested how they connect, [see here].
 //! favannat can be used to evaluate other data structures of yours, too, if they are [`favannat::network::NetworkLike`]. ;)
 //!
+//! The optional 'population' feature adds a basic generational loop, see `Population`.
+//!
+//! The optional 'schemars' feature adds `Parameters::json_schema` to validate config files in editors.
+//!
+//! The optional 'save' feature adds `Parameters::save` to write parameters as TOML, JSON or YAML.
+//!
+//! The optional 'rayon' feature adds parallel batch operations for many genomes at once, e.g. `Genome::mutate_batch`, `Genome::offspring_batch` and `CompatibilityDistance::distance_matrix`.
+//!
+//! Without the 'favannat' feature a genome can still be evaluated via the built-in [`Genome::compile`], which returns a simple [`Network`].
+//!
 //! [thesis]: https://www.silvan.codes/SET-NEAT_Thesis.pdf
 //! [this crate]: https://crates.io/crates/favannat
 //! [crossover]: `Genome::cross_in`
@@ -144,18 +166,40 @@
 //! [favannat]: https://docs.rs/favannat
 //! [see here]: https://github.com/SilvanCodes/set-genome/blob/main/src/favannat_impl.rs
 
+// the older tests build their genes from `vec!`s and pass references to references
+#![cfg_attr(test, allow(clippy::useless_vec, clippy::needless_borrow))]
+
 pub use genes::{activations, Connection, Id, Node};
-pub use genome::{CompatibilityDistance, Genome};
-pub use mutations::{MutationError, MutationResult, Mutations};
-pub use parameters::{Parameters, Structure};
-use rand::{rngs::SmallRng, thread_rng, SeedableRng};
+pub use genome::{
+    ActivationChange, Adjacency, AdjacencyError, CompatibilityDistance, CompatibilityFactors,
+    CrossoverStrategy, DistanceBreakdown, DotOptions, EnabledChange, Genome, GenomeDiff,
+    GenomeViolation, Layers, NodeIds, NodeParameterChange, Normalization, PruneOptions,
+    PruneSummary, RankDirection, SparseMatrix, WeightChange,
+};
+pub use id_registry::{Divergence, IdRegistry, Innovation, StructuralEvent};
+pub use mutations::{
+    GeneChanges, MutationError, MutationOutcome, MutationReport, MutationResult, MutationStatus,
+    Mutations,
+};
+pub use network::{CompileError, Network};
+pub use parameters::{
+    BoundaryHandling, InvalidValue, ParameterViolation, Parameters, ParametersError,
+    PerturbationDistribution, Structure, WeightBounds, WeightConfig, WeightInitialization,
+};
+#[cfg(feature = "population")]
+pub use population::{Member, Population, PopulationParameters, Species};
+use rand::{rngs::SmallRng, thread_rng, Rng, SeedableRng};
 
 #[cfg(feature = "favannat")]
 mod favannat_impl;
 mod genes;
 mod genome;
+mod id_registry;
 mod mutations;
+mod network;
 mod parameters;
+#[cfg(feature = "population")]
+mod population;
 
 impl Genome {
     /// Initialization connects the configured percent of inputs nodes to output nodes, i.e. it creates connection genes with random weights.
@@ -164,8 +208,15 @@ impl Genome {
     }
 
     pub fn initialized(parameters: &Parameters) -> Self {
+        Self::initialized_with_rng(parameters, &mut SmallRng::from_rng(thread_rng()).unwrap())
+    }
+
+    /// Same as [`Genome::initialized`] but draws all randomness from the supplied `rng`.
+    ///
+    /// Together with [`Genome::mutate_with_rng`] and [`Genome::cross_in_with_rng`] this allows for fully reproducible runs.
+    pub fn initialized_with_rng(parameters: &Parameters, rng: &mut impl Rng) -> Self {
         let mut genome = Genome::new(&parameters.structure);
-        genome.init(&parameters.structure);
+        genome.init_with_weights(&parameters.structure, &parameters.weights, rng);
         genome
     }
 
@@ -190,12 +241,182 @@ impl Genome {
     /// ```
     ///
     pub fn mutate(&mut self, parameters: &Parameters) -> MutationResult {
-        let rng = &mut SmallRng::from_rng(thread_rng()).unwrap();
+        self.mutate_with_rng(parameters, &mut SmallRng::from_rng(thread_rng()).unwrap())
+    }
 
+    /// Same as [`Genome::mutate`] but draws all randomness from the supplied `rng`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    /// use set_genome::{Genome, Parameters};
+    ///
+    /// let parameters = Parameters::default();
+    ///
+    /// let mut genome_0 = Genome::initialized_with_rng(&parameters, &mut SmallRng::seed_from_u64(42));
+    /// let mut genome_1 = Genome::initialized_with_rng(&parameters, &mut SmallRng::seed_from_u64(42));
+    ///
+    /// genome_0.mutate_with_rng(&parameters, &mut SmallRng::seed_from_u64(7));
+    /// genome_1.mutate_with_rng(&parameters, &mut SmallRng::seed_from_u64(7));
+    ///
+    /// assert_eq!(genome_0, genome_1);
+    /// ```
+    pub fn mutate_with_rng(
+        &mut self,
+        parameters: &Parameters,
+        rng: &mut impl Rng,
+    ) -> MutationResult {
         for mutation in &parameters.mutations {
             // gamble for application of mutation right here instead of in mutate() ??
-            mutation.mutate(self, rng)?
+            mutation.mutate_with_weights(&parameters.weights, self, rng)?
         }
         Ok(())
     }
+
+    /// Apply all mutations listed in the [`Parameters`] like [`Genome::mutate`], but do not stop at the first failing mutation.
+    /// Instead every mutation is attempted and the returned [`MutationReport`] tells which mutations were applied, skipped or failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use set_genome::{Genome, Parameters};
+    ///
+    /// let parameters = Parameters::default();
+    ///
+    /// let mut genome = Genome::initialized(&parameters);
+    ///
+    /// let report = genome.mutate_reported(&parameters);
+    ///
+    /// assert_eq!(report.outcomes.len(), parameters.mutations.len());
+    ///
+    /// for (outcome, changes) in report.applied() {
+    ///     println!("{:?} added nodes {:?}", outcome.mutation, changes.added_nodes);
+    /// }
+    /// ```
+    pub fn mutate_reported(&mut self, parameters: &Parameters) -> MutationReport {
+        self.mutate_reported_with_rng(parameters, &mut SmallRng::from_rng(thread_rng()).unwrap())
+    }
+
+    /// Same as [`Genome::mutate_reported`] but draws all randomness from the supplied `rng`.
+    pub fn mutate_reported_with_rng(
+        &mut self,
+        parameters: &Parameters,
+        rng: &mut impl Rng,
+    ) -> MutationReport {
+        MutationReport::record(&parameters.mutations, &parameters.weights, self, rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use crate::{
+        activations::Activation, BoundaryHandling, Genome, Mutations, Parameters,
+        PerturbationDistribution, Structure,
+    };
+
+    fn reproducible_parameters() -> Parameters {
+        Parameters {
+            structure: Structure {
+                number_of_inputs: 5,
+                number_of_outputs: 3,
+                percent_of_connected_inputs: 0.6,
+                ..Default::default()
+            },
+            mutations: vec![
+                Mutations::ChangeWeights {
+                    chance: 1.0,
+                    percent_perturbed: 0.5,
+                    standard_deviation: 0.1,
+                    distribution: PerturbationDistribution::Normal,
+                    boundary_handling: BoundaryHandling::Reflect,
+                },
+                Mutations::ChangeActivation {
+                    chance: 0.2,
+                    activation_pool: Activation::all(),
+                },
+                Mutations::AddNode {
+                    chance: 0.2,
+                    activation_pool: Activation::all(),
+                },
+                Mutations::AddConnection { chance: 0.3 },
+                Mutations::AddRecurrentConnection { chance: 0.1 },
+                Mutations::DuplicateNode { chance: 0.05 },
+                Mutations::RemoveNode { chance: 0.05 },
+                Mutations::RemoveConnection { chance: 0.05 },
+                Mutations::RemoveRecurrentConnection { chance: 0.05 },
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn evolve(seed: u64) -> Genome {
+        let parameters = reproducible_parameters();
+        let rng = &mut SmallRng::seed_from_u64(seed);
+
+        let mut genome_0 = Genome::initialized_with_rng(&parameters, rng);
+        let mut genome_1 = Genome::initialized_with_rng(&parameters, rng);
+
+        for _ in 0..200 {
+            // failing mutations are expected and still consume randomness deterministically
+            let _ = genome_0.mutate_with_rng(&parameters, rng);
+            let _ = genome_1.mutate_with_rng(&parameters, rng);
+            genome_0 = genome_0.cross_in_with_rng(&genome_1, rng);
+        }
+
+        genome_0
+    }
+
+    // `Genome` equality only considers gene identities, so compare every field down to the bits.
+    fn assert_bit_identical(genome_0: &Genome, genome_1: &Genome) {
+        assert_eq!(genome_0, genome_1);
+
+        for (node_0, node_1) in genome_0.nodes().zip(genome_1.nodes()) {
+            assert_eq!(node_0.id, node_1.id);
+            assert_eq!(node_0.order, node_1.order);
+            assert_eq!(node_0.activation, node_1.activation);
+            assert_eq!(node_0.id_counter, node_1.id_counter);
+            assert_eq!(node_0.bias.to_bits(), node_1.bias.to_bits());
+            assert_eq!(node_0.response.to_bits(), node_1.response.to_bits());
+        }
+
+        for (connection_0, connection_1) in genome_0.connections().zip(genome_1.connections()) {
+            assert_eq!(connection_0.id(), connection_1.id());
+            assert_eq!(connection_0.weight.to_bits(), connection_1.weight.to_bits());
+            assert_eq!(connection_0.id_counter, connection_1.id_counter);
+            assert_eq!(connection_0.enabled, connection_1.enabled);
+        }
+    }
+
+    #[test]
+    fn same_seed_same_initialization() {
+        let parameters = reproducible_parameters();
+
+        let genome_0 = Genome::initialized_with_rng(&parameters, &mut SmallRng::seed_from_u64(42));
+        let genome_1 = Genome::initialized_with_rng(&parameters, &mut SmallRng::seed_from_u64(42));
+
+        assert_bit_identical(&genome_0, &genome_1);
+    }
+
+    #[test]
+    fn same_seed_same_evolution() {
+        let genome_0 = evolve(42);
+        let genome_1 = evolve(42);
+
+        assert!(!genome_0.hidden.is_empty());
+        assert_bit_identical(&genome_0, &genome_1);
+    }
+
+    #[test]
+    fn different_seed_different_evolution() {
+        let genome_0 = evolve(42);
+        let genome_1 = evolve(43);
+
+        assert!(genome_0.connections().zip(genome_1.connections()).any(
+            |(connection_0, connection_1)| connection_0.id() != connection_1.id()
+                || connection_0.weight.to_bits() != connection_1.weight.to_bits()
+        ));
+    }
 }
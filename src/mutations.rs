@@ -1,9 +1,14 @@
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
-use crate::{genes::Activation, genome::Genome};
+use crate::{
+    genes::Activation,
+    genome::{Genome, PruneOptions},
+    parameters::{BoundaryHandling, PerturbationDistribution, WeightConfig},
+};
 
 pub use self::error::MutationError;
+pub use self::report::{GeneChanges, MutationOutcome, MutationReport, MutationStatus};
 
 pub type MutationResult = Result<(), MutationError>;
 
@@ -11,26 +16,39 @@ mod add_connection;
 mod add_node;
 mod add_recurrent_connection;
 mod change_activation;
+mod change_node_parameters;
 mod change_weights;
 mod duplicate_node;
 mod error;
+mod prune;
+mod re_enable_connection;
 mod remove_connection;
 mod remove_node;
 mod remove_recurrent_connection;
+mod replace_weights;
+mod report;
+mod toggle_connection;
 
 /// Lists all possible mutations with their corresponding parameters.
 ///
 /// Each mutation acts as a self-contained unit and has to be listed in the [`crate::Parameters::mutations`] field in order to take effect when calling [`crate::Genome::mutate_with`].
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
 pub enum Mutations {
-    /// See [`Mutations::change_weights`].
+    /// See [`Mutations::change_weights_with`].
     ChangeWeights {
         chance: f64,
         percent_perturbed: f64,
         standard_deviation: f64,
+        #[serde(default)]
+        distribution: PerturbationDistribution,
+        #[serde(default)]
+        boundary_handling: BoundaryHandling,
     },
+    /// See [`Mutations::replace_weights`].
+    ReplaceWeights { chance: f64, percent_replaced: f64 },
     /// See [`Mutations::change_activation`].
     ChangeActivation {
         chance: f64,
@@ -53,6 +71,23 @@ pub enum Mutations {
     RemoveRecurrentConnection { chance: f64 },
     /// See [`Mutations::duplicate_node`].
     DuplicateNode { chance: f64 },
+    /// See [`Mutations::change_node_parameters`].
+    ChangeNodeParameters {
+        chance: f64,
+        percent_perturbed: f64,
+        bias_standard_deviation: f64,
+        response_standard_deviation: f64,
+    },
+    /// See [`Mutations::prune`].
+    Prune {
+        chance: f64,
+        #[serde(default)]
+        options: PruneOptions,
+    },
+    /// See [`Mutations::toggle_connection`].
+    ToggleConnection { chance: f64 },
+    /// See [`Mutations::re_enable_connection`].
+    ReEnableConnection { chance: f64 },
 }
 
 impl Mutations {
@@ -60,63 +95,172 @@ impl Mutations {
     /// The user needs to supply some RNG manually when using this method directly.
     /// Use [`crate::Genome::mutate`] as the default API.
     pub fn mutate(&self, genome: &mut Genome, rng: &mut impl Rng) -> MutationResult {
+        self.mutate_with_weights(&WeightConfig::default(), genome, rng)
+    }
+
+    /// Same as [`Mutations::mutate`] but samples new weights according to `weights`, usually [`crate::Parameters::weights`].
+    pub fn mutate_with_weights(
+        &self,
+        weights: &WeightConfig,
+        genome: &mut Genome,
+        rng: &mut impl Rng,
+    ) -> MutationResult {
+        if rng.gen::<f64>() < self.chance() {
+            self.apply_with_weights(weights, genome, rng)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the chance of the mutation to happen when calling [`Mutations::mutate`].
+    pub fn chance(&self) -> f64 {
+        match *self {
+            Mutations::ChangeWeights { chance, .. }
+            | Mutations::ReplaceWeights { chance, .. }
+            | Mutations::ChangeNodeParameters { chance, .. }
+            | Mutations::Prune { chance, .. }
+            | Mutations::ChangeActivation { chance, .. }
+            | Mutations::AddNode { chance, .. }
+            | Mutations::AddConnection { chance }
+            | Mutations::AddRecurrentConnection { chance }
+            | Mutations::RemoveNode { chance }
+            | Mutations::RemoveConnection { chance }
+            | Mutations::RemoveRecurrentConnection { chance }
+            | Mutations::DuplicateNode { chance }
+            | Mutations::ToggleConnection { chance }
+            | Mutations::ReEnableConnection { chance } => chance,
+        }
+    }
+
+    /// Mutate a [`Genome`] unconditionally, i.e. ignoring the `chance` field.
+    ///
+    /// In debug builds a genome that is [valid] before the mutation is validated again afterwards and any violation panics, as it is introduced by the mutation.
+    ///
+    /// [valid]: `Genome::validate`
+    pub fn apply(&self, genome: &mut Genome, rng: &mut impl Rng) -> MutationResult {
+        self.apply_with_weights(&WeightConfig::default(), genome, rng)
+    }
+
+    /// Same as [`Mutations::apply`] but samples new weights according to `weights`, usually [`crate::Parameters::weights`].
+    pub fn apply_with_weights(
+        &self,
+        weights: &WeightConfig,
+        genome: &mut Genome,
+        rng: &mut impl Rng,
+    ) -> MutationResult {
+        // invalid input, e.g. read from disk, is not the fault of the mutation
+        let check = cfg!(debug_assertions) && genome.validate().is_ok();
+
+        let result = self.apply_unchecked(weights, genome, rng);
+        if self.changes_depths() {
+            genome.order_hidden_nodes();
+        }
+
+        if check {
+            if let Err(violations) = genome.validate() {
+                panic!(
+                    "genome is invalid after applying {:?}: {:?}",
+                    self, violations
+                );
+            }
+        }
+
+        result
+    }
+
+    /// Whether the mutation can change the feed-forward depth of hidden nodes and thus their order.
+    fn changes_depths(&self) -> bool {
+        match self {
+            Mutations::AddNode { .. }
+            | Mutations::AddConnection { .. }
+            | Mutations::RemoveNode { .. }
+            | Mutations::RemoveConnection { .. }
+            | Mutations::DuplicateNode { .. }
+            | Mutations::Prune { .. }
+            | Mutations::ToggleConnection { .. }
+            | Mutations::ReEnableConnection { .. } => true,
+            Mutations::ChangeWeights { .. }
+            | Mutations::ReplaceWeights { .. }
+            | Mutations::ChangeNodeParameters { .. }
+            | Mutations::ChangeActivation { .. }
+            | Mutations::AddRecurrentConnection { .. }
+            | Mutations::RemoveRecurrentConnection { .. } => false,
+        }
+    }
+
+    fn apply_unchecked(
+        &self,
+        weights: &WeightConfig,
+        genome: &mut Genome,
+        rng: &mut impl Rng,
+    ) -> MutationResult {
         match self {
             &Mutations::ChangeWeights {
-                chance,
                 percent_perturbed,
                 standard_deviation,
+                distribution,
+                boundary_handling,
+                ..
             } => {
-                if rng.gen::<f64>() < chance {
-                    Self::change_weights(percent_perturbed, standard_deviation, genome, rng);
-                }
+                Self::change_weights_with(
+                    percent_perturbed,
+                    standard_deviation,
+                    distribution,
+                    boundary_handling,
+                    weights,
+                    genome,
+                    rng,
+                );
+                Ok(())
             }
-            Mutations::AddNode {
-                chance,
-                activation_pool,
+            &Mutations::ReplaceWeights {
+                percent_replaced, ..
+            } => {
+                Self::replace_weights(percent_replaced, weights, genome, rng);
+                Ok(())
+            }
+            &Mutations::ChangeNodeParameters {
+                percent_perturbed,
+                bias_standard_deviation,
+                response_standard_deviation,
+                ..
             } => {
-                if rng.gen::<f64>() < *chance {
-                    Self::add_node(activation_pool, genome, rng)
-                }
+                Self::change_node_parameters(
+                    percent_perturbed,
+                    bias_standard_deviation,
+                    response_standard_deviation,
+                    genome,
+                    rng,
+                );
+                Ok(())
             }
-            &Mutations::AddConnection { chance } => {
-                if rng.gen::<f64>() < chance {
-                    return Self::add_connection(genome, rng);
-                }
+            Mutations::AddNode {
+                activation_pool, ..
+            } => Self::add_node(activation_pool, genome, rng),
+            Mutations::AddConnection { .. } => {
+                Self::add_connection_with_weights(weights, genome, rng)
             }
-            &Mutations::AddRecurrentConnection { chance } => {
-                if rng.gen::<f64>() < chance {
-                    return Self::add_recurrent_connection(genome, rng);
-                }
+            Mutations::AddRecurrentConnection { .. } => {
+                Self::add_recurrent_connection_with_weights(weights, genome, rng)
             }
             Mutations::ChangeActivation {
-                chance,
-                activation_pool,
+                activation_pool, ..
             } => {
-                if rng.gen::<f64>() < *chance {
-                    Self::change_activation(activation_pool, genome, rng)
-                }
-            }
-            &Mutations::RemoveNode { chance } => {
-                if rng.gen::<f64>() < chance {
-                    return Self::remove_node(genome, rng);
-                }
-            }
-            &Mutations::RemoveConnection { chance } => {
-                if rng.gen::<f64>() < chance {
-                    return Self::remove_connection(genome, rng);
-                }
+                Self::change_activation(activation_pool, genome, rng);
+                Ok(())
             }
-            &Mutations::RemoveRecurrentConnection { chance } => {
-                if rng.gen::<f64>() < chance {
-                    return Self::remove_recurrent_connection(genome, rng);
-                }
+            Mutations::RemoveNode { .. } => Self::remove_node(genome, rng),
+            Mutations::RemoveConnection { .. } => Self::remove_connection(genome, rng),
+            Mutations::RemoveRecurrentConnection { .. } => {
+                Self::remove_recurrent_connection(genome, rng)
             }
-            &Mutations::DuplicateNode { chance } => {
-                if rng.gen::<f64>() < chance {
-                    return Self::duplicate_node(genome, rng);
-                }
+            Mutations::DuplicateNode { .. } => Self::duplicate_node(genome, rng),
+            Mutations::Prune { options, .. } => {
+                Self::prune(options, genome);
+                Ok(())
             }
+            Mutations::ToggleConnection { .. } => Self::toggle_connection(genome, rng),
+            Mutations::ReEnableConnection { .. } => Self::re_enable_connection(genome, rng),
         }
-        Ok(())
     }
 }
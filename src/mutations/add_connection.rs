@@ -1,6 +1,6 @@
 use rand::{seq::SliceRandom, Rng};
 
-use crate::{genes::Connection, genome::Genome};
+use crate::{genes::Connection, genome::Genome, parameters::WeightConfig};
 
 use super::{MutationError, MutationResult, Mutations};
 
@@ -8,11 +8,21 @@ impl Mutations {
     /// This mutation adds a new feed-forward connection to the genome, should it be possible.
     /// It is possible when any two nodes[^details] are not yet connected with a feed-forward connection.
     ///
-    /// [^details]: "any two nodes" is technically not correct as the start node for the connection has to come from the intersection of input and hidden nodes and the end node has to come from the intersection of the hidden and output nodes.
+    /// [^details]: "any two nodes" is technically not correct as the start node for the connection has to come from the intersection of input, bias and hidden nodes and the end node has to come from the intersection of the hidden and output nodes.
     pub fn add_connection(genome: &mut Genome, rng: &mut impl Rng) -> MutationResult {
+        Self::add_connection_with_weights(&WeightConfig::default(), genome, rng)
+    }
+
+    /// Same as [`Mutations::add_connection`] but samples the weight of the new connection according to `weights`.
+    pub fn add_connection_with_weights(
+        weights: &WeightConfig,
+        genome: &mut Genome,
+        rng: &mut impl Rng,
+    ) -> MutationResult {
         let mut possible_start_nodes = genome
             .inputs
             .iter()
+            .chain(genome.bias.iter())
             .chain(genome.hidden.iter())
             .collect::<Vec<_>>();
         possible_start_nodes.shuffle(rng);
@@ -37,7 +47,7 @@ impl Mutations {
                 // add new feed-forward connection
                 assert!(genome.feed_forward.insert(Connection::new(
                     start_node.id,
-                    Connection::weight_perturbation(0.0, 0.1, rng),
+                    weights.new_weight(rng),
                     end_node.id,
                 )));
                 return Ok(());
@@ -62,6 +72,24 @@ mod tests {
         assert_eq!(genome.feed_forward.len(), 1);
     }
 
+    #[test]
+    fn add_connection_from_bias() {
+        let mut parameters = Parameters::default();
+        parameters.structure.bias = true;
+
+        let mut genome = Genome::uninitialized(&parameters);
+
+        // one input and the bias can each connect to the single output
+        Mutations::add_connection(&mut genome, &mut thread_rng()).expect("add_connection");
+        Mutations::add_connection(&mut genome, &mut thread_rng()).expect("add_connection");
+
+        let bias = genome.bias.as_ref().unwrap();
+        assert!(genome
+            .feed_forward
+            .iter()
+            .any(|connection| connection.input == bias.id));
+    }
+
     #[test]
     fn dont_add_same_connection_twice() {
         let mut genome = Genome::uninitialized(&Parameters::default());
@@ -1,18 +1,31 @@
-use rand::{prelude::SliceRandom, Rng};
+use rand::{
+    prelude::{IteratorRandom, SliceRandom},
+    Rng,
+};
 
 use crate::{
     genes::{Activation, Connection, Node},
     genome::Genome,
 };
 
-use super::Mutations;
+use super::{MutationError, MutationResult, Mutations};
 
 impl Mutations {
-    /// This mutation adds a new node to the genome by "splitting" an existing connection, i.e. the existing connection gets "re-routed" via the new node and the weight of the split connection is set to zero.
-    /// The connection leading into the new node is of weight 1.0 and the connection originating from the new node has the same weight as the split connection (before it is zeroed).
-    pub fn add_node(activation_pool: &[Activation], genome: &mut Genome, rng: &mut impl Rng) {
+    /// This mutation adds a new node to the genome by "splitting" an enabled connection, i.e. the existing connection gets "re-routed" via the new node and the split connection is disabled.
+    /// The connection leading into the new node is of weight 1.0 and the connection originating from the new node has the same weight as the split connection.
+    pub fn add_node(
+        activation_pool: &[Activation],
+        genome: &mut Genome,
+        rng: &mut impl Rng,
+    ) -> MutationResult {
         // select an connection gene and split
-        let mut random_connection = genome.feed_forward.random(rng).cloned().unwrap();
+        let mut random_connection = genome
+            .feed_forward
+            .iter()
+            .filter(|connection| connection.enabled)
+            .choose(rng)
+            .cloned()
+            .ok_or(MutationError::CouldNotAddNode)?;
 
         let mut id = random_connection.next_id();
 
@@ -39,9 +52,10 @@ impl Mutations {
         // insert new node into genome
         assert!(genome.hidden.insert(new_node));
 
-        // update weight to zero to 'deactivate' connnection
-        random_connection.weight = 0.0;
+        random_connection.enabled = false;
         genome.feed_forward.replace(random_connection);
+
+        Ok(())
     }
 }
 
@@ -49,15 +63,42 @@ impl Mutations {
 mod tests {
     use rand::thread_rng;
 
-    use crate::{activations::Activation, Genome, Mutations, Parameters};
+    use crate::{
+        activations::Activation, genes::Connection, Genome, MutationError, Mutations, Parameters,
+    };
 
     #[test]
     fn add_random_node() {
         let mut genome = Genome::initialized(&Parameters::default());
 
-        Mutations::add_node(&Activation::all(), &mut genome, &mut thread_rng());
+        Mutations::add_node(&Activation::all(), &mut genome, &mut thread_rng()).unwrap();
 
         assert_eq!(genome.feed_forward.len(), 3);
+        assert_eq!(
+            genome
+                .feed_forward
+                .iter()
+                .filter(|connection| !connection.enabled)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn do_not_split_disabled_connection() {
+        let mut genome = Genome::initialized(&Parameters::default());
+
+        for connection in genome.feed_forward.drain().collect::<Vec<_>>() {
+            genome.feed_forward.insert(Connection {
+                enabled: false,
+                ..connection
+            });
+        }
+
+        assert_eq!(
+            Mutations::add_node(&Activation::all(), &mut genome, &mut thread_rng()),
+            Err(MutationError::CouldNotAddNode)
+        );
     }
 
     #[test]
@@ -65,8 +106,8 @@ mod tests {
         let mut genome1 = Genome::initialized(&Parameters::default());
         let mut genome2 = Genome::initialized(&Parameters::default());
 
-        Mutations::add_node(&Activation::all(), &mut genome1, &mut thread_rng());
-        Mutations::add_node(&Activation::all(), &mut genome2, &mut thread_rng());
+        Mutations::add_node(&Activation::all(), &mut genome1, &mut thread_rng()).unwrap();
+        Mutations::add_node(&Activation::all(), &mut genome2, &mut thread_rng()).unwrap();
 
         assert_eq!(genome1.hidden, genome2.hidden);
     }
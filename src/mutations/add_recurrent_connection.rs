@@ -1,6 +1,6 @@
 use rand::{seq::SliceRandom, Rng};
 
-use crate::{genes::Connection, genome::Genome};
+use crate::{genes::Connection, genome::Genome, parameters::WeightConfig};
 
 use super::{MutationError, MutationResult, Mutations};
 
@@ -9,7 +9,17 @@ impl Mutations {
     /// It is possible when any two nodes [^details] are not yet connected with a recurrent connection.
     ///
     /// [^details]: "any two nodes" is technically not correct as the end node has to come from the intersection of the hidden and output nodes.
+    /// The bias node is never the start of a recurrent connection, as its constant signal is covered by feed-forward connections.
     pub fn add_recurrent_connection(genome: &mut Genome, rng: &mut impl Rng) -> MutationResult {
+        Self::add_recurrent_connection_with_weights(&WeightConfig::default(), genome, rng)
+    }
+
+    /// Same as [`Mutations::add_recurrent_connection`] but samples the weight of the new connection according to `weights`.
+    pub fn add_recurrent_connection_with_weights(
+        weights: &WeightConfig,
+        genome: &mut Genome,
+        rng: &mut impl Rng,
+    ) -> MutationResult {
         let mut possible_start_nodes = genome
             .inputs
             .iter()
@@ -33,7 +43,7 @@ impl Mutations {
             }) {
                 assert!(genome.recurrent.insert(Connection::new(
                     start_node.id,
-                    Connection::weight_perturbation(0.0, 0.1, rng),
+                    weights.new_weight(rng),
                     end_node.id,
                 )));
                 return Ok(());
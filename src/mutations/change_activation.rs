@@ -16,15 +16,15 @@ impl Mutations {
         rng: &mut impl Rng,
     ) {
         if let Some(node) = genome.hidden.random(rng) {
-            let updated = Node::hidden(
-                node.id,
-                activation_pool
+            let updated = Node {
+                activation: activation_pool
                     .iter()
                     .filter(|&&activation| activation != node.activation)
                     .choose(rng)
                     .cloned()
                     .unwrap_or(node.activation),
-            );
+                ..node.clone()
+            };
 
             genome.hidden.replace(updated);
         }
@@ -42,7 +42,7 @@ mod tests {
         let mut genome = Genome::initialized(&Parameters::default());
         let activation_pool = Activation::all();
 
-        Mutations::add_node(&activation_pool, &mut genome, &mut thread_rng());
+        Mutations::add_node(&activation_pool, &mut genome, &mut thread_rng()).unwrap();
 
         let old_activation = genome.hidden.iter().next().unwrap().activation;
 
@@ -0,0 +1,61 @@
+use rand::Rng;
+
+use super::Mutations;
+use crate::{genome::Genome, parameters::PerturbationDistribution};
+
+impl Mutations {
+    /// This mutation alters the bias and response of `percent_perturbed` hidden and output nodes.
+    /// Both are perturbed by values sampled from a gaussian distribution with the respective standard deviation.
+    pub fn change_node_parameters(
+        percent_perturbed: f64,
+        bias_standard_deviation: f64,
+        response_standard_deviation: f64,
+        genome: &mut Genome,
+        rng: &mut impl Rng,
+    ) {
+        for nodes in [&mut genome.hidden, &mut genome.outputs] {
+            let change_amount = (percent_perturbed * nodes.len() as f64).ceil() as usize;
+
+            *nodes = nodes
+                .drain_into_random(rng)
+                .enumerate()
+                .map(|(index, mut node)| {
+                    if index < change_amount {
+                        node.bias +=
+                            PerturbationDistribution::Normal.sample(bias_standard_deviation, rng);
+                        node.response += PerturbationDistribution::Normal
+                            .sample(response_standard_deviation, rng);
+                    }
+                    node
+                })
+                .collect();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use crate::{activations::Activation, Genome, Mutations, Parameters};
+
+    #[test]
+    fn change_node_parameters() {
+        let mut genome = Genome::initialized(&Parameters::default());
+        let rng = &mut thread_rng();
+
+        Mutations::add_node(&Activation::all(), &mut genome, rng).unwrap();
+        Mutations::change_node_parameters(1.0, 1.0, 1.0, &mut genome, rng);
+
+        assert!(genome
+            .hidden
+            .iter()
+            .chain(genome.outputs.iter())
+            .all(|node| node.bias.abs() > 0.0 && (node.response - 1.0).abs() > 0.0));
+        // inputs are unaffected
+        assert!(genome
+            .inputs
+            .iter()
+            .all(|node| node.bias == 0.0 && node.response == 1.0));
+    }
+}
@@ -1,15 +1,41 @@
 use rand::Rng;
 
 use super::Mutations;
-use crate::genome::Genome;
+use crate::{
+    genome::Genome,
+    parameters::{BoundaryHandling, PerturbationDistribution, WeightConfig},
+};
 
 impl Mutations {
     /// This mutation alters `percent_perturbed` connection weights sampled from a gaussian distribution with given `standard_deviation`.
+    /// The weights are clamped to the default [`crate::WeightBounds`], see [`Mutations::change_weights_with`].
     pub fn change_weights(
         percent_perturbed: f64,
         standard_deviation: f64,
         genome: &mut Genome,
         rng: &mut impl Rng,
+    ) {
+        Self::change_weights_with(
+            percent_perturbed,
+            standard_deviation,
+            PerturbationDistribution::Normal,
+            BoundaryHandling::Clamp,
+            &WeightConfig::default(),
+            genome,
+            rng,
+        )
+    }
+
+    /// This mutation alters `percent_perturbed` connection weights by perturbations sampled from `distribution` with given `standard_deviation`.
+    /// The weights stay within the configured [`crate::WeightBounds`] according to `boundary_handling`.
+    pub fn change_weights_with(
+        percent_perturbed: f64,
+        standard_deviation: f64,
+        distribution: PerturbationDistribution,
+        boundary_handling: BoundaryHandling,
+        weights: &WeightConfig,
+        genome: &mut Genome,
+        rng: &mut impl Rng,
     ) {
         let change_feed_forward_amount =
             (percent_perturbed * genome.feed_forward.len() as f64).ceil() as usize;
@@ -22,7 +48,13 @@ impl Mutations {
             .enumerate()
             .map(|(index, mut connection)| {
                 if index < change_feed_forward_amount {
-                    connection.perturb_weight(standard_deviation, rng);
+                    connection.perturb_weight_with(
+                        distribution,
+                        standard_deviation,
+                        boundary_handling,
+                        &weights.bounds,
+                        rng,
+                    );
                 }
                 connection
             })
@@ -34,7 +66,13 @@ impl Mutations {
             .enumerate()
             .map(|(index, mut connection)| {
                 if index < change_recurrent_amount {
-                    connection.perturb_weight(standard_deviation, rng);
+                    connection.perturb_weight_with(
+                        distribution,
+                        standard_deviation,
+                        boundary_handling,
+                        &weights.bounds,
+                        rng,
+                    );
                 }
                 connection
             })
@@ -46,7 +84,9 @@ impl Mutations {
 mod tests {
     use rand::thread_rng;
 
-    use crate::{Genome, Mutations, Parameters};
+    use crate::{
+        BoundaryHandling, Genome, Mutations, Parameters, PerturbationDistribution, WeightConfig,
+    };
 
     #[test]
     fn change_weights() {
@@ -60,4 +100,25 @@ mod tests {
             (old_weight - genome.feed_forward.iter().next().unwrap().weight).abs() > f64::EPSILON
         );
     }
+
+    #[test]
+    fn change_weights_with() {
+        let mut genome = Genome::initialized(&Parameters::default());
+
+        let old_weight = genome.feed_forward.iter().next().unwrap().weight;
+
+        Mutations::change_weights_with(
+            1.0,
+            1.0,
+            PerturbationDistribution::Normal,
+            BoundaryHandling::Reflect,
+            &WeightConfig::default(),
+            &mut genome,
+            &mut thread_rng(),
+        );
+
+        assert!(
+            (old_weight - genome.feed_forward.iter().next().unwrap().weight).abs() > f64::EPSILON
+        );
+    }
 }
@@ -20,8 +20,12 @@ impl Mutations {
                 id = random_hidden_node.next_id()
             }
 
-            // construct new node gene
-            let new_node = Node::hidden(id, random_hidden_node.activation);
+            // construct new node gene, computing the same value as the duplicated one
+            let new_node = Node {
+                bias: random_hidden_node.bias,
+                response: random_hidden_node.response,
+                ..Node::hidden(id, random_hidden_node.activation)
+            };
 
             // duplicate outgoing feedforward connections
             let mut outgoing_feedforward_connections = genome
@@ -45,7 +49,7 @@ impl Mutations {
 
             // update weights
             for connection in outgoing_feedforward_connections.iter_mut() {
-                connection.weight = connection.weight / 2.0;
+                connection.weight /= 2.0;
                 let mut new_connection = connection.clone();
                 new_connection.input = new_node.id;
                 new_feedworward_connections.push(new_connection);
@@ -89,7 +93,7 @@ impl Mutations {
 
             // update weights
             for connection in outgoing_recurrent_connections.iter_mut() {
-                connection.weight = connection.weight / 2.0;
+                connection.weight /= 2.0;
                 let mut new_connection = connection.clone();
                 new_connection.input = new_node.id;
                 new_recurrent_connections.push(new_connection);
@@ -145,7 +149,7 @@ mod tests {
         let mut genome = Genome::initialized(&Parameters::default());
         assert_eq!(genome.feed_forward.len(), 1);
 
-        Mutations::add_node(&Activation::all(), &mut genome, &mut thread_rng());
+        Mutations::add_node(&Activation::all(), &mut genome, &mut thread_rng()).unwrap();
         assert_eq!(genome.hidden.len(), 1);
         assert_eq!(genome.feed_forward.len(), 3);
 
@@ -172,10 +176,10 @@ mod tests {
         let mut genome1 = Genome::initialized(&Parameters::default());
         let mut genome2 = Genome::initialized(&Parameters::default());
 
-        Mutations::add_node(&Activation::all(), &mut genome1, &mut thread_rng());
+        Mutations::add_node(&Activation::all(), &mut genome1, &mut thread_rng()).unwrap();
         assert!(Mutations::duplicate_node(&mut genome1, &mut thread_rng()).is_ok());
 
-        Mutations::add_node(&Activation::all(), &mut genome2, &mut thread_rng());
+        Mutations::add_node(&Activation::all(), &mut genome2, &mut thread_rng()).unwrap();
         assert!(Mutations::duplicate_node(&mut genome2, &mut thread_rng()).is_ok());
 
         assert_eq!(genome1.hidden, genome2.hidden);
@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-#[derive(Error, Debug, PartialEq, Eq)]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum MutationError {
     #[error("No two nodes could be connected by a new feed-forward connection.")]
     CouldNotAddFeedForwardConnection,
@@ -14,4 +14,10 @@ pub enum MutationError {
     CouldNotRemoveRecurrentConnection,
     #[error("No hidden node to duplicate was present in the genome.")]
     CouldNotDuplicateNode,
+    #[error("No enabled feed-forward connection to split present in the genome.")]
+    CouldNotAddNode,
+    #[error("No connection to toggle present in the genome.")]
+    CouldNotToggleConnection,
+    #[error("No disabled connection present in the genome.")]
+    CouldNotReEnableConnection,
 }
@@ -0,0 +1,65 @@
+use crate::{genome::Genome, PruneOptions};
+
+use super::Mutations;
+
+impl Mutations {
+    /// Removes dead and unreachable structure, see [`Genome::prune`].
+    pub fn prune(options: &PruneOptions, genome: &mut Genome) {
+        genome.prune(options);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::{Config, File, FileFormat};
+
+    use crate::{Mutations, Parameters, PruneOptions};
+
+    #[test]
+    fn read_prune_mutation() {
+        let mut config = Config::new();
+        config
+            .merge(File::from_str(
+                r#"
+                [structure]
+                number_of_inputs = 2
+                number_of_outputs = 1
+                percent_of_connected_inputs = 1.0
+                outputs_activation = "Tanh"
+                seed = 42
+
+                [[mutations]]
+                type = "prune"
+                chance = 0.1
+
+                [[mutations]]
+                type = "prune"
+                chance = 0.1
+
+                [mutations.options]
+                weight_threshold = 0.05
+                "#,
+                FileFormat::Toml,
+            ))
+            .unwrap();
+
+        let parameters: Parameters = config.try_into().unwrap();
+
+        assert_eq!(
+            parameters.mutations,
+            vec![
+                Mutations::Prune {
+                    chance: 0.1,
+                    options: PruneOptions::default(),
+                },
+                Mutations::Prune {
+                    chance: 0.1,
+                    options: PruneOptions {
+                        weight_threshold: 0.05,
+                        remove_disabled: true,
+                    },
+                },
+            ]
+        );
+    }
+}
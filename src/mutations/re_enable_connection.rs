@@ -0,0 +1,56 @@
+use rand::{prelude::IteratorRandom, Rng};
+
+use crate::Genome;
+
+use super::{MutationError, MutationResult, Mutations};
+
+impl Mutations {
+    /// Enables a disabled connection, feed-forward or recurrent, e.g. one that was split by [`Mutations::add_node`].
+    pub fn re_enable_connection(genome: &mut Genome, rng: &mut impl Rng) -> MutationResult {
+        let (is_recurrent, mut connection) = genome
+            .feed_forward
+            .iter()
+            .map(|connection| (false, connection))
+            .chain(genome.recurrent.iter().map(|connection| (true, connection)))
+            .filter(|(_, connection)| !connection.enabled)
+            .choose(rng)
+            .map(|(is_recurrent, connection)| (is_recurrent, connection.clone()))
+            .ok_or(MutationError::CouldNotReEnableConnection)?;
+
+        connection.enabled = true;
+
+        if is_recurrent {
+            genome.recurrent.replace(connection);
+        } else {
+            genome.feed_forward.replace(connection);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use crate::{activations::Activation, mutations::MutationError, Genome, Mutations, Parameters};
+
+    #[test]
+    fn re_enable_split_connection() {
+        let mut genome = Genome::initialized(&Parameters::default());
+        let rng = &mut thread_rng();
+
+        assert_eq!(
+            Mutations::re_enable_connection(&mut genome, rng),
+            Err(MutationError::CouldNotReEnableConnection)
+        );
+
+        Mutations::add_node(&Activation::all(), &mut genome, rng).unwrap();
+
+        assert!(Mutations::re_enable_connection(&mut genome, rng).is_ok());
+        assert!(genome
+            .feed_forward
+            .iter()
+            .all(|connection| connection.enabled));
+    }
+}
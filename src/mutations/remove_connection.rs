@@ -6,7 +6,7 @@ use super::{MutationError, MutationResult, Mutations};
 
 impl Mutations {
     /// Removes a connection, should this be possible without introducing dangling structure.
-    /// Dangling means the in- or out-degree of any hidden node is zero, i.e. it neither can receive nor propagate a signal.
+    /// Dangling means the in- or out-degree of any hidden node, counting enabled connections only, is zero, i.e. it neither can receive nor propagate a signal.
     /// If it is not possible, no connection will be removed.
     pub fn remove_connection(genome: &mut Genome, rng: &mut impl Rng) -> MutationResult {
         if let Some(removable_connection) = &genome
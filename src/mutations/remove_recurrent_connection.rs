@@ -5,18 +5,32 @@ use crate::Genome;
 use super::{MutationError, MutationResult, Mutations};
 
 impl Mutations {
-    /// Removes a recurrent connection if at least one is present in the genome.
-    /// Does nothing when no recurrent connections exist.
+    /// Removes a recurrent connection, should this be possible without introducing dangling structure.
+    /// Dangling means the in- or out-degree of any hidden node, counting enabled connections only, is zero, i.e. it neither can receive nor propagate a signal.
+    /// If it is not possible, no connection will be removed.
     pub fn remove_recurrent_connection(genome: &mut Genome, rng: &mut impl Rng) -> MutationResult {
+        let is_hidden = |id| genome.hidden.iter().any(|node| node.id == id);
+
         if let Some(removable_connection) = &genome
             .recurrent
             .iter()
             // make iterator wrap
             .cycle()
-            // randomly offset into the iterator to choose any node
+            // randomly offset into the iterator to choose any connection
             .skip((rng.gen::<f64>() * (genome.recurrent.len()) as f64).floor() as usize)
+            // just loop every value once
+            .take(genome.recurrent.len())
+            .find(|removal_candidate| {
+                (!is_hidden(removal_candidate.output)
+                    || genome
+                        .has_alternative_input(removal_candidate.output, removal_candidate.input))
+                    && (!is_hidden(removal_candidate.input)
+                        || genome.has_alternative_output(
+                            removal_candidate.input,
+                            removal_candidate.output,
+                        ))
+            })
             .cloned()
-            .next()
         {
             assert!(genome.recurrent.remove(removable_connection));
             Ok(())
@@ -90,4 +104,42 @@ mod tests {
             unreachable!()
         }
     }
+
+    #[test]
+    fn do_not_strand_hidden_node() {
+        let mut genome = Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            hidden: Genes(
+                [Node::hidden(Id(2), Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            outputs: Genes(
+                [Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [Connection::new(Id(2), 1.0, Id(1))]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            // only input of the hidden node
+            recurrent: Genes(
+                [Connection::new(Id(0), 1.0, Id(2))]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Mutations::remove_recurrent_connection(&mut genome, &mut thread_rng()),
+            Err(MutationError::CouldNotRemoveRecurrentConnection)
+        );
+    }
 }
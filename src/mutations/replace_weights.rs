@@ -0,0 +1,92 @@
+use rand::Rng;
+
+use super::Mutations;
+use crate::{genome::Genome, parameters::WeightConfig};
+
+impl Mutations {
+    /// This mutation replaces `percent_replaced` connection weights with fresh values, sampled like the weights of new connections.
+    /// Other than [`Mutations::change_weights`] the new value does not depend on the old one.
+    pub fn replace_weights(
+        percent_replaced: f64,
+        weights: &WeightConfig,
+        genome: &mut Genome,
+        rng: &mut impl Rng,
+    ) {
+        let replace_feed_forward_amount =
+            (percent_replaced * genome.feed_forward.len() as f64).ceil() as usize;
+        let replace_recurrent_amount =
+            (percent_replaced * genome.recurrent.len() as f64).ceil() as usize;
+
+        genome.feed_forward = genome
+            .feed_forward
+            .drain_into_random(rng)
+            .enumerate()
+            .map(|(index, mut connection)| {
+                if index < replace_feed_forward_amount {
+                    connection.weight = weights.new_weight(rng);
+                }
+                connection
+            })
+            .collect();
+
+        genome.recurrent = genome
+            .recurrent
+            .drain_into_random(rng)
+            .enumerate()
+            .map(|(index, mut connection)| {
+                if index < replace_recurrent_amount {
+                    connection.weight = weights.new_weight(rng);
+                }
+                connection
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use crate::{
+        activations::Activation,
+        genes::{Connection, Genes, Id, Node},
+        Genome, Mutations, WeightConfig,
+    };
+
+    #[test]
+    fn replace_all_weights() {
+        let mut genome = Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            outputs: Genes(
+                [Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [Connection::new(Id(0), 5.0, Id(1))]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            recurrent: Genes(
+                [Connection::new(Id(1), 5.0, Id(1))]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+
+        // default weights are bounded to [-1, 1], so every replaced weight ends up within
+        let weights = WeightConfig::default();
+
+        Mutations::replace_weights(1.0, &weights, &mut genome, &mut thread_rng());
+
+        assert!(genome
+            .feed_forward
+            .iter()
+            .chain(genome.recurrent.iter())
+            .all(|connection| weights.bounds.contains(connection.weight)));
+    }
+}
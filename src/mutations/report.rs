@@ -0,0 +1,269 @@
+use std::collections::HashSet;
+
+use rand::Rng;
+
+use crate::{genes::Id, genome::Genome, parameters::WeightConfig};
+
+use super::{MutationError, Mutations};
+
+/// Identities of the genes a single mutation added to or removed from a genome.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GeneChanges {
+    pub added_nodes: Vec<Id>,
+    pub removed_nodes: Vec<Id>,
+    pub added_feed_forward: Vec<(Id, Id)>,
+    pub removed_feed_forward: Vec<(Id, Id)>,
+    pub added_recurrent: Vec<(Id, Id)>,
+    pub removed_recurrent: Vec<(Id, Id)>,
+}
+
+impl GeneChanges {
+    fn between(before: &Snapshot, after: &Snapshot) -> Self {
+        fn sorted<T: Copy + Ord>(items: impl Iterator<Item = T>) -> Vec<T> {
+            let mut items = items.collect::<Vec<_>>();
+            items.sort_unstable();
+            items
+        }
+
+        Self {
+            added_nodes: sorted(after.nodes.difference(&before.nodes).copied()),
+            removed_nodes: sorted(before.nodes.difference(&after.nodes).copied()),
+            added_feed_forward: sorted(
+                after.feed_forward.difference(&before.feed_forward).copied(),
+            ),
+            removed_feed_forward: sorted(
+                before.feed_forward.difference(&after.feed_forward).copied(),
+            ),
+            added_recurrent: sorted(after.recurrent.difference(&before.recurrent).copied()),
+            removed_recurrent: sorted(before.recurrent.difference(&after.recurrent).copied()),
+        }
+    }
+
+    /// Is true when no gene was added or removed, e.g. when only weights or activations changed.
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.added_feed_forward.is_empty()
+            && self.removed_feed_forward.is_empty()
+            && self.added_recurrent.is_empty()
+            && self.removed_recurrent.is_empty()
+    }
+}
+
+/// What happened to a single entry of [`crate::Parameters::mutations`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MutationStatus {
+    /// The mutation was applied and changed the listed genes.
+    Applied(GeneChanges),
+    /// The mutation did not happen due to its `chance`.
+    Skipped,
+    /// The mutation was attempted but was not possible.
+    Failed(MutationError),
+}
+
+/// The outcome of a single entry of [`crate::Parameters::mutations`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutationOutcome {
+    /// Position of the mutation in [`crate::Parameters::mutations`].
+    pub index: usize,
+    pub mutation: Mutations,
+    pub status: MutationStatus,
+}
+
+/// Lists the outcome of every configured mutation, see [`crate::Genome::mutate_reported`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MutationReport {
+    pub outcomes: Vec<MutationOutcome>,
+}
+
+impl MutationReport {
+    /// Runs every mutation in order, regardless of earlier failures, and records what happened.
+    pub(crate) fn record(
+        mutations: &[Mutations],
+        weights: &WeightConfig,
+        genome: &mut Genome,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let outcomes = mutations
+            .iter()
+            .enumerate()
+            .map(|(index, mutation)| {
+                // gamble the same way as `Mutations::mutate` to consume identical randomness
+                let status = if rng.gen::<f64>() < mutation.chance() {
+                    let before = Snapshot::of(genome);
+                    match mutation.apply_with_weights(weights, genome, rng) {
+                        Ok(()) => MutationStatus::Applied(before.changes_to(genome)),
+                        Err(error) => MutationStatus::Failed(error),
+                    }
+                } else {
+                    MutationStatus::Skipped
+                };
+
+                MutationOutcome {
+                    index,
+                    mutation: mutation.clone(),
+                    status,
+                }
+            })
+            .collect();
+
+        Self { outcomes }
+    }
+
+    /// Iterates all mutations that were applied.
+    pub fn applied(&self) -> impl Iterator<Item = (&MutationOutcome, &GeneChanges)> {
+        self.outcomes
+            .iter()
+            .filter_map(|outcome| match &outcome.status {
+                MutationStatus::Applied(changes) => Some((outcome, changes)),
+                _ => None,
+            })
+    }
+
+    /// Iterates all mutations that were skipped due to their chance.
+    pub fn skipped(&self) -> impl Iterator<Item = &MutationOutcome> {
+        self.outcomes
+            .iter()
+            .filter(|outcome| outcome.status == MutationStatus::Skipped)
+    }
+
+    /// Iterates all mutations that failed together with the reason.
+    pub fn failed(&self) -> impl Iterator<Item = (&MutationOutcome, &MutationError)> {
+        self.outcomes
+            .iter()
+            .filter_map(|outcome| match &outcome.status {
+                MutationStatus::Failed(error) => Some((outcome, error)),
+                _ => None,
+            })
+    }
+
+    /// Is true when no mutation failed.
+    pub fn is_ok(&self) -> bool {
+        self.failed().next().is_none()
+    }
+}
+
+/// Gene identities present in a genome at some point in time.
+struct Snapshot {
+    nodes: HashSet<Id>,
+    feed_forward: HashSet<(Id, Id)>,
+    recurrent: HashSet<(Id, Id)>,
+}
+
+impl Snapshot {
+    fn of(genome: &Genome) -> Self {
+        Self {
+            nodes: genome.hidden.iter().map(|node| node.id).collect(),
+            feed_forward: genome
+                .feed_forward
+                .iter()
+                .map(|connection| connection.id())
+                .collect(),
+            recurrent: genome
+                .recurrent
+                .iter()
+                .map(|connection| connection.id())
+                .collect(),
+        }
+    }
+
+    fn changes_to(&self, genome: &Genome) -> GeneChanges {
+        GeneChanges::between(self, &Snapshot::of(genome))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use crate::{
+        activations::Activation, Genome, MutationError, MutationStatus, Mutations, Parameters,
+    };
+
+    #[test]
+    fn failure_does_not_abort_later_mutations() {
+        let parameters = Parameters {
+            mutations: vec![
+                Mutations::RemoveRecurrentConnection { chance: 1.0 },
+                Mutations::AddConnection { chance: 1.0 },
+                Mutations::AddNode {
+                    chance: 0.0,
+                    activation_pool: Activation::all(),
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut genome = Genome::uninitialized(&parameters);
+
+        let report = genome.mutate_reported(&parameters);
+
+        assert_eq!(
+            report.outcomes[0].status,
+            MutationStatus::Failed(MutationError::CouldNotRemoveRecurrentConnection)
+        );
+        assert_eq!(report.failed().count(), 1);
+
+        let (outcome, changes) = report.applied().next().unwrap();
+        assert_eq!(outcome.index, 1);
+        assert_eq!(changes.added_feed_forward.len(), 1);
+        assert_eq!(genome.feed_forward.len(), 1);
+
+        assert_eq!(report.skipped().next().unwrap().index, 2);
+        assert!(!report.is_ok());
+    }
+
+    #[test]
+    fn report_added_and_removed_genes() {
+        let parameters = Parameters {
+            mutations: vec![
+                Mutations::AddNode {
+                    chance: 1.0,
+                    activation_pool: Activation::all(),
+                },
+                Mutations::ReEnableConnection { chance: 1.0 },
+                Mutations::RemoveNode { chance: 1.0 },
+            ],
+            ..Default::default()
+        };
+
+        let mut genome = Genome::initialized(&parameters);
+
+        let report = genome.mutate_reported(&parameters);
+
+        let (_, added) = report.applied().next().unwrap();
+        assert_eq!(added.added_nodes.len(), 1);
+        assert_eq!(added.added_feed_forward.len(), 2);
+        assert!(added.removed_feed_forward.is_empty());
+
+        // the split connection is enabled again, so the new node can be removed again
+        let (_, removed) = report.applied().nth(2).unwrap();
+        assert_eq!(removed.removed_nodes, added.added_nodes);
+        assert_eq!(removed.removed_feed_forward, added.added_feed_forward);
+        assert!(genome.hidden.is_empty());
+    }
+
+    #[test]
+    fn same_randomness_as_mutate() {
+        let parameters = Parameters::default();
+
+        let mut genome_0 =
+            Genome::initialized_with_rng(&parameters, &mut SmallRng::seed_from_u64(42));
+        let mut genome_1 = genome_0.clone();
+
+        let rng_0 = &mut SmallRng::seed_from_u64(42);
+        let rng_1 = &mut SmallRng::seed_from_u64(42);
+
+        for _ in 0..50 {
+            if genome_0.mutate_with_rng(&parameters, rng_0).is_ok() {
+                assert!(genome_1
+                    .mutate_reported_with_rng(&parameters, rng_1)
+                    .is_ok());
+            } else {
+                break;
+            }
+        }
+
+        assert_eq!(genome_0, genome_1);
+    }
+}
@@ -0,0 +1,79 @@
+use rand::{prelude::IteratorRandom, Rng};
+
+use crate::Genome;
+
+use super::{MutationError, MutationResult, Mutations};
+
+impl Mutations {
+    /// Enables a disabled or disables an enabled connection, feed-forward or recurrent.
+    pub fn toggle_connection(genome: &mut Genome, rng: &mut impl Rng) -> MutationResult {
+        let (is_recurrent, mut connection) = genome
+            .feed_forward
+            .iter()
+            .map(|connection| (false, connection))
+            .chain(genome.recurrent.iter().map(|connection| (true, connection)))
+            .choose(rng)
+            .map(|(is_recurrent, connection)| (is_recurrent, connection.clone()))
+            .ok_or(MutationError::CouldNotToggleConnection)?;
+
+        connection.enabled = !connection.enabled;
+
+        if is_recurrent {
+            genome.recurrent.replace(connection);
+        } else {
+            genome.feed_forward.replace(connection);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use crate::{
+        activations::Activation,
+        genes::{Connection, Genes, Id, Node},
+        mutations::MutationError,
+        Genome, Mutations,
+    };
+
+    #[test]
+    fn toggle_twice() {
+        let mut genome = Genome {
+            inputs: Genes([Node::input(Id(0), 0)].iter().cloned().collect()),
+            outputs: Genes(
+                [Node::output(Id(1), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [Connection::new(Id(0), 1.0, Id(1))]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+
+        let rng = &mut thread_rng();
+
+        assert!(Mutations::toggle_connection(&mut genome, rng).is_ok());
+        assert!(!genome.feed_forward.iter().next().unwrap().enabled);
+
+        assert!(Mutations::toggle_connection(&mut genome, rng).is_ok());
+        assert!(genome.feed_forward.iter().next().unwrap().enabled);
+    }
+
+    #[test]
+    fn can_not_toggle_without_connections() {
+        let mut genome = Genome::default();
+
+        assert_eq!(
+            Mutations::toggle_connection(&mut genome, &mut thread_rng()),
+            Err(MutationError::CouldNotToggleConnection)
+        );
+    }
+}
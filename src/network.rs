@@ -0,0 +1,396 @@
+//! A lightweight phenotype of the [`Genome`] that does not require the `favannat` feature.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::{
+    genes::{Connection, Id},
+    genome::Genome,
+};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CompileError {
+    #[error("The feed-forward connections of the genome contain a cycle.")]
+    FeedForwardCycle,
+    #[error("A connection references the node {0:?} which is not part of the genome.")]
+    UnknownNode(Id),
+}
+
+/// A computation step of the network, i.e. a single non-input node and everything it depends on.
+#[derive(Debug, Clone)]
+struct Step {
+    node: usize,
+    activation: fn(f64) -> f64,
+    bias: f64,
+    response: f64,
+    feed_forward: Vec<(usize, f64)>,
+    recurrent: Vec<(usize, f64)>,
+}
+
+/// Executable form of a [`Genome`], see [`Genome::compile`].
+///
+/// Feed-forward connections propagate the values of the current evaluation,
+/// recurrent connections propagate the values of the previous evaluation.
+/// The network therefore keeps state between calls to [`Network::evaluate`] until it is [`Network::reset`].
+#[derive(Debug, Clone)]
+pub struct Network {
+    inputs: Vec<usize>,
+    bias: Option<usize>,
+    outputs: Vec<usize>,
+    steps: Vec<Step>,
+    state: Vec<f64>,
+}
+
+impl Genome {
+    /// Compiles the genome into a [`Network`] that can be evaluated.
+    ///
+    /// Nodes are evaluated in topological order of the feed-forward connections, disabled connections are left out.
+    /// Inputs are expected in the order given by the input nodes `order` field, outputs are returned likewise.
+    /// A bias node, if present, does not take an input and always emits one.
+    /// Every other node computes `activation(bias + response * sum_of_inputs)` from its own bias and response.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use set_genome::{Genome, Parameters};
+    ///
+    /// let parameters = Parameters::basic(3, 2);
+    ///
+    /// let genome = Genome::initialized(&parameters);
+    ///
+    /// let mut network = genome.compile().expect("valid genome");
+    ///
+    /// let output = network.evaluate(&[0.0, 1.0, 2.0]);
+    ///
+    /// assert_eq!(output.len(), 2);
+    /// ```
+    pub fn compile(&self) -> Result<Network, CompileError> {
+        let index: HashMap<Id, usize> = self
+            .nodes()
+            .enumerate()
+            .map(|(index, node)| (node.id, index))
+            .collect();
+
+        let lookup = |id: Id| index.get(&id).copied().ok_or(CompileError::UnknownNode(id));
+
+        let mut feed_forward: HashMap<usize, Vec<(usize, f64)>> = HashMap::new();
+        let mut recurrent: HashMap<usize, Vec<(usize, f64)>> = HashMap::new();
+        // number of not yet computed feed-forward dependencies per node
+        let mut pending = vec![0; index.len()];
+        let mut dependents: HashMap<usize, Vec<usize>> = HashMap::new();
+
+        let enabled = |connection: &&Connection| connection.enabled;
+
+        for connection in self
+            .feed_forward
+            .as_sorted_vec()
+            .into_iter()
+            .filter(enabled)
+        {
+            let (input, output) = (lookup(connection.input)?, lookup(connection.output)?);
+            feed_forward
+                .entry(output)
+                .or_default()
+                .push((input, connection.weight));
+            dependents.entry(input).or_default().push(output);
+            pending[output] += 1;
+        }
+
+        for connection in self.recurrent.as_sorted_vec().into_iter().filter(enabled) {
+            let (input, output) = (lookup(connection.input)?, lookup(connection.output)?);
+            recurrent
+                .entry(output)
+                .or_default()
+                .push((input, connection.weight));
+        }
+
+        let inputs = self
+            .inputs
+            .as_ordered_vec()
+            .into_iter()
+            .map(|node| index[&node.id])
+            .collect::<Vec<_>>();
+        let bias = self.bias.as_ref().map(|node| index[&node.id]);
+        let outputs = self
+            .outputs
+            .as_ordered_vec()
+            .into_iter()
+            .map(|node| index[&node.id])
+            .collect::<Vec<_>>();
+
+        // Kahn's algorithm, starting from every node without feed-forward dependencies
+        let nodes = self.nodes().collect::<Vec<_>>();
+        let mut ready = (0..nodes.len())
+            .filter(|&node| pending[node] == 0)
+            .collect::<Vec<_>>();
+        let mut order = Vec::with_capacity(nodes.len());
+
+        while let Some(node) = ready.pop() {
+            order.push(node);
+            for &dependent in dependents.get(&node).into_iter().flatten() {
+                pending[dependent] -= 1;
+                if pending[dependent] == 0 {
+                    ready.push(dependent);
+                }
+            }
+        }
+
+        if order.len() < nodes.len() {
+            return Err(CompileError::FeedForwardCycle);
+        }
+
+        let steps = order
+            .into_iter()
+            .filter(|node| !inputs.contains(node) && Some(*node) != bias)
+            .map(|node| Step {
+                node,
+                activation: nodes[node].activation.function(),
+                bias: nodes[node].bias,
+                response: nodes[node].response,
+                feed_forward: feed_forward.remove(&node).unwrap_or_default(),
+                recurrent: recurrent.remove(&node).unwrap_or_default(),
+            })
+            .collect();
+
+        Ok(Network {
+            inputs,
+            bias,
+            outputs,
+            steps,
+            state: vec![0.0; nodes.len()],
+        })
+    }
+}
+
+impl Network {
+    /// Evaluates the network on the given input and returns the values of the output nodes.
+    ///
+    /// Panics if the number of inputs does not match the number of input nodes.
+    pub fn evaluate(&mut self, input: &[f64]) -> Vec<f64> {
+        assert_eq!(
+            input.len(),
+            self.inputs.len(),
+            "expected {} inputs, got {}",
+            self.inputs.len(),
+            input.len()
+        );
+
+        let previous = self.state.clone();
+
+        for (&node, &value) in self.inputs.iter().zip(input) {
+            self.state[node] = value;
+        }
+
+        if let Some(bias) = self.bias {
+            self.state[bias] = 1.0;
+        }
+
+        for step in &self.steps {
+            let sum = step
+                .feed_forward
+                .iter()
+                .map(|&(input, weight)| self.state[input] * weight)
+                .chain(
+                    step.recurrent
+                        .iter()
+                        .map(|&(input, weight)| previous[input] * weight),
+                )
+                .sum::<f64>();
+
+            self.state[step.node] = (step.activation)(step.bias + step.response * sum);
+        }
+
+        self.outputs.iter().map(|&node| self.state[node]).collect()
+    }
+
+    /// Clears the state carried by recurrent connections.
+    pub fn reset(&mut self) {
+        self.state.iter_mut().for_each(|value| *value = 0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        activations::Activation,
+        genes::{Connection, Genes, Id, Node},
+        Genome, Parameters,
+    };
+
+    use super::CompileError;
+
+    fn linear_genome() -> Genome {
+        Genome {
+            inputs: Genes(
+                [Node::input(Id(0), 0), Node::input(Id(1), 1)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            hidden: Genes(
+                [Node::hidden(Id(3), Activation::Relu)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            outputs: Genes(
+                [Node::output(Id(2), 0, Activation::Linear)]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            feed_forward: Genes(
+                [
+                    Connection::new(Id(0), 1.0, Id(3)),
+                    Connection::new(Id(1), -1.0, Id(3)),
+                    Connection::new(Id(3), 2.0, Id(2)),
+                    Connection::new(Id(1), 0.5, Id(2)),
+                ]
+                .iter()
+                .cloned()
+                .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn evaluate_feed_forward() {
+        let mut network = linear_genome().compile().unwrap();
+
+        // relu(3 - 1) * 2 + 1 * 0.5
+        assert!((network.evaluate(&[3.0, 1.0])[0] - 4.5).abs() < f64::EPSILON);
+        // relu(1 - 3) * 2 + 3 * 0.5
+        assert!((network.evaluate(&[1.0, 3.0])[0] - 1.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn evaluate_bias() {
+        let mut genome = linear_genome();
+        genome.bias = Some(Node::bias(Id(4)));
+        genome
+            .feed_forward
+            .insert(Connection::new(Id(4), 0.25, Id(2)));
+
+        let mut network = genome.compile().unwrap();
+
+        assert!((network.evaluate(&[3.0, 1.0])[0] - 4.75).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn skip_disabled_connections() {
+        let mut genome = linear_genome();
+        genome.feed_forward.replace(Connection {
+            enabled: false,
+            ..Connection::new(Id(1), 0.5, Id(2))
+        });
+
+        let mut network = genome.compile().unwrap();
+
+        // relu(3 - 1) * 2
+        assert!((network.evaluate(&[3.0, 1.0])[0] - 4.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn apply_node_bias_and_response() {
+        let mut genome = linear_genome();
+        genome.outputs.replace(Node {
+            bias: 1.0,
+            response: 0.5,
+            ..Node::output(Id(2), 0, Activation::Linear)
+        });
+
+        let mut network = genome.compile().unwrap();
+
+        // 1 + 0.5 * (relu(3 - 1) * 2 + 1 * 0.5)
+        assert!((network.evaluate(&[3.0, 1.0])[0] - 3.25).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn recurrent_state_persists_until_reset() {
+        let mut genome = linear_genome();
+        genome.recurrent.insert(Connection::new(Id(2), 1.0, Id(2)));
+
+        let mut network = genome.compile().unwrap();
+
+        assert!((network.evaluate(&[3.0, 1.0])[0] - 4.5).abs() < f64::EPSILON);
+        assert!((network.evaluate(&[3.0, 1.0])[0] - 9.0).abs() < f64::EPSILON);
+
+        network.reset();
+
+        assert!((network.evaluate(&[3.0, 1.0])[0] - 4.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn detect_feed_forward_cycle() {
+        let mut genome = linear_genome();
+        genome
+            .feed_forward
+            .insert(Connection::new(Id(2), 1.0, Id(3)));
+
+        assert_eq!(
+            genome.compile().unwrap_err(),
+            CompileError::FeedForwardCycle
+        );
+    }
+
+    #[test]
+    fn detect_unknown_node() {
+        let mut genome = linear_genome();
+        genome
+            .feed_forward
+            .insert(Connection::new(Id(0), 1.0, Id(42)));
+
+        assert_eq!(
+            genome.compile().unwrap_err(),
+            CompileError::UnknownNode(Id(42))
+        );
+    }
+
+    #[cfg(feature = "favannat")]
+    #[test]
+    fn same_output_as_favannat() {
+        use favannat::{MatrixRecurrentFabricator, StatefulEvaluator, StatefulFabricator};
+
+        use crate::Mutations;
+
+        let mut parameters = Parameters::default();
+        parameters.mutations.push(Mutations::ChangeNodeParameters {
+            chance: 0.5,
+            percent_perturbed: 0.5,
+            bias_standard_deviation: 0.5,
+            response_standard_deviation: 0.5,
+        });
+        let mut genome = Genome::initialized(&parameters);
+
+        for _ in 0..100 {
+            let _ = genome.mutate(&parameters);
+        }
+
+        let mut network = genome.compile().unwrap();
+        let mut evaluator = MatrixRecurrentFabricator::fabricate(&genome.favannat_net()).unwrap();
+
+        for step in 0..10 {
+            let input = vec![step as f64 / 10.0];
+            let expected = evaluator.evaluate(input.clone());
+            let actual = network.evaluate(&input);
+
+            for (expected, actual) in expected.iter().zip(actual.iter()) {
+                assert!((expected - actual).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn compile_mutated_genome() {
+        let parameters = Parameters::basic(4, 2);
+        let mut genome = Genome::initialized(&parameters);
+
+        for _ in 0..100 {
+            let _ = genome.mutate(&parameters);
+        }
+
+        assert_eq!(genome.compile().unwrap().evaluate(&[1.0; 4]).len(), 2);
+    }
+}
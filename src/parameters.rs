@@ -1,7 +1,18 @@
-use crate::{genes::Activation, mutations::Mutations};
-use config::{Config, ConfigError, File};
+use crate::{
+    genes::{Activation, Connection},
+    genome::CompatibilityFactors,
+    mutations::Mutations,
+};
+use rand::Rng;
+use rand_distr::{Cauchy, Distribution, Exp1, StandardNormal};
 use serde::{Deserialize, Serialize};
 
+pub use self::files::ParametersError;
+pub use self::validation::{InvalidValue, ParameterViolation};
+
+mod files;
+mod validation;
+
 /// This struct captures configuration about the basic ANN structure and [available mutations].
 ///
 /// It can be constructed manually or from a `.toml` file.
@@ -12,7 +23,11 @@ use serde::{Deserialize, Serialize};
 ///
 /// The following lists everything that is possible to specify:
 /// ```
-/// use set_genome::{Parameters, Structure, Mutations, activations::Activation};
+/// use set_genome::{
+///     BoundaryHandling, CompatibilityFactors, Normalization, Parameters, PerturbationDistribution,
+///     PruneOptions, Structure, Mutations, WeightBounds, WeightConfig, WeightInitialization,
+///     activations::Activation,
+/// };
 ///
 /// let parameters = Parameters {
 ///     structure: Structure {
@@ -20,13 +35,16 @@ use serde::{Deserialize, Serialize};
 ///         number_of_outputs: 3,
 ///         percent_of_connected_inputs: 1.0,
 ///         outputs_activation: Activation::Tanh,
-///         seed: 42
+///         seed: 42,
+///         bias: true,
 ///     },
 ///     mutations: vec![
 ///         Mutations::ChangeWeights {
 ///         chance: 1.0,
 ///         percent_perturbed: 0.5,
 ///         standard_deviation: 0.1,
+///         distribution: PerturbationDistribution::Normal,
+///         boundary_handling: BoundaryHandling::Reflect,
 ///         },
 ///         Mutations::ChangeActivation {
 ///             chance: 0.05,
@@ -63,7 +81,45 @@ use serde::{Deserialize, Serialize};
 ///         Mutations::RemoveConnection { chance: 0.001 },
 ///         Mutations::AddRecurrentConnection { chance: 0.01 },
 ///         Mutations::RemoveRecurrentConnection { chance: 0.001 },
+///         Mutations::ReplaceWeights {
+///             chance: 0.1,
+///             percent_replaced: 0.1,
+///         },
+///         Mutations::ChangeNodeParameters {
+///             chance: 0.1,
+///             percent_perturbed: 0.1,
+///             bias_standard_deviation: 0.1,
+///             response_standard_deviation: 0.05,
+///         },
+///         Mutations::ToggleConnection { chance: 0.01 },
+///         Mutations::ReEnableConnection { chance: 0.01 },
+///         Mutations::Prune {
+///             chance: 0.01,
+///             options: PruneOptions {
+///                 weight_threshold: 0.01,
+///                 remove_disabled: true,
+///             },
+///         },
 ///     ],
+///     weights: WeightConfig {
+///         initialization: WeightInitialization::Normal {
+///             standard_deviation: 0.1,
+///         },
+///         standard_deviation: 0.1,
+///         bounds: WeightBounds::Bounded {
+///             min: -1.0,
+///             max: 1.0,
+///         },
+///     },
+///     compatibility: CompatibilityFactors {
+///         connections: 1.0,
+///         recurrent_connections: Some(0.5),
+///         disjoint_nodes: 0.5,
+///         weights: 0.5,
+///         activations: 0.5,
+///         node_parameters: 0.5,
+///         normalization: Normalization::LargerGenome,
+///     },
 /// };
 /// ```
 ///
@@ -76,6 +132,27 @@ use serde::{Deserialize, Serialize};
 /// number_of_outputs = 2
 /// percent_of_connected_inputs = 1.0
 /// outputs_activation = "Tanh"
+/// bias = true
+///
+/// [weights]
+/// standard_deviation = 0.1
+///
+/// [weights.initialization]
+/// type = "he"
+///
+/// [weights.bounds]
+/// type = "unbounded"
+///
+/// # optional, every factor has a default
+/// [compatibility]
+/// connections = 1.0
+/// # optional, counts recurrent connections as regular ones when missing
+/// recurrent_connections = 0.5
+/// disjoint_nodes = 0.5
+/// weights = 0.5
+/// activations = 0.5
+/// node_parameters = 0.5
+/// normalization = "larger_genome"
 ///
 /// [[mutations]]
 /// type = "add_connection"
@@ -109,7 +186,11 @@ use serde::{Deserialize, Serialize};
 /// type = "change_weights"
 /// chance = 1.0
 /// percent_perturbed = 0.5
-/// standard_deviation = 0.1,
+/// standard_deviation = 0.1
+/// # optional, defaults to "normal"
+/// distribution = "laplace"
+/// # optional, defaults to "reflect"
+/// boundary_handling = "resample"
 ///
 /// [[mutations]]
 /// type = "change_activation"
@@ -134,6 +215,34 @@ use serde::{Deserialize, Serialize};
 /// [[mutations]]
 /// type = "remove_recurrent_connection"
 /// chance = 0.001
+///
+/// [[mutations]]
+/// type = "replace_weights"
+/// chance = 0.1
+/// percent_replaced = 0.1
+///
+/// [[mutations]]
+/// type = "change_node_parameters"
+/// chance = 0.1
+/// percent_perturbed = 0.1
+/// bias_standard_deviation = 0.1
+/// response_standard_deviation = 0.05
+///
+/// [[mutations]]
+/// type = "toggle_connection"
+/// chance = 0.01
+///
+/// [[mutations]]
+/// type = "re_enable_connection"
+/// chance = 0.01
+///
+/// [[mutations]]
+/// type = "prune"
+/// chance = 0.01
+///
+/// [mutations.options]
+/// weight_threshold = 0.01
+/// remove_disabled = true
 /// ```
 ///
 /// And then read the file:
@@ -142,14 +251,24 @@ use serde::{Deserialize, Serialize};
 /// // let parameters = Parameters::new("path/to/file");
 /// ```
 ///
+/// Parameters can be written to TOML, JSON or YAML files via [`Parameters::save`],
+/// and several files can be layered and overridden from the environment via [`Parameters::layered`].
+///
 /// [available mutations]: `Mutations`
 ///
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Parameters {
     /// Describes basic structure of the ANN.
     pub structure: Structure,
     /// List of mutations that execute on [`crate::Genome::mutate_with`]
     pub mutations: Vec<Mutations>,
+    /// Describes how connection weights are initialized and bounded.
+    #[serde(default)]
+    pub weights: WeightConfig,
+    /// Factors of the [`crate::CompatibilityDistance`], see [`crate::CompatibilityDistance::from_parameters`].
+    #[serde(default)]
+    pub compatibility: CompatibilityFactors,
 }
 
 impl Default for Parameters {
@@ -161,6 +280,8 @@ impl Default for Parameters {
                     chance: 1.0,
                     percent_perturbed: 0.5,
                     standard_deviation: 0.1,
+                    distribution: PerturbationDistribution::Normal,
+                    boundary_handling: BoundaryHandling::Reflect,
                 },
                 Mutations::ChangeActivation {
                     chance: 0.05,
@@ -195,6 +316,8 @@ impl Default for Parameters {
                 Mutations::AddConnection { chance: 0.1 },
                 Mutations::AddRecurrentConnection { chance: 0.01 },
             ],
+            weights: WeightConfig::default(),
+            compatibility: CompatibilityFactors::default(),
         }
     }
 }
@@ -210,6 +333,8 @@ impl Parameters {
                     chance: 1.0,
                     percent_perturbed: 0.5,
                     standard_deviation: 0.1,
+                    distribution: PerturbationDistribution::Normal,
+                    boundary_handling: BoundaryHandling::Reflect,
                 },
                 Mutations::AddNode {
                     chance: 0.01,
@@ -217,12 +342,15 @@ impl Parameters {
                 },
                 Mutations::AddConnection { chance: 0.1 },
             ],
+            weights: WeightConfig::default(),
+            compatibility: CompatibilityFactors::default(),
         }
     }
 }
 
 /// This struct describes the invariants of the ANN structure.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Structure {
     /// Number of input nodes.
     pub number_of_inputs: usize,
@@ -234,6 +362,9 @@ pub struct Structure {
     pub outputs_activation: Activation,
     /// Seed to generate the initial node ids.
     pub seed: u64,
+    /// Adds a bias node, which emits a constant signal of one, initially connected to all output nodes.
+    #[serde(default)]
+    pub bias: bool,
 }
 
 impl Default for Structure {
@@ -244,6 +375,7 @@ impl Default for Structure {
             percent_of_connected_inputs: 1.0,
             outputs_activation: Activation::Tanh,
             seed: 42,
+            bias: false,
         }
     }
 }
@@ -259,14 +391,635 @@ impl Structure {
     }
 }
 
+/// Describes how connection weights are initialized and which values they may take.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WeightConfig {
+    /// Distribution of the weights created by [`crate::Genome::init`].
+    pub initialization: WeightInitialization,
+    /// Standard deviation of the weights of connections added by mutations.
+    pub standard_deviation: f64,
+    /// Range all weights are kept in.
+    pub bounds: WeightBounds,
+}
+
+impl Default for WeightConfig {
+    fn default() -> Self {
+        Self {
+            initialization: WeightInitialization::Normal {
+                standard_deviation: 0.1,
+            },
+            standard_deviation: 0.1,
+            bounds: WeightBounds::default(),
+        }
+    }
+}
+
+impl WeightConfig {
+    /// Samples the weight of an initial connection into a node with `fan_in` incoming connections.
+    pub fn initial_weight(&self, fan_in: usize, rng: &mut impl Rng) -> f64 {
+        let fan_in = fan_in.max(1) as f64;
+
+        let standard_deviation = match self.initialization {
+            WeightInitialization::Normal { standard_deviation } => standard_deviation,
+            WeightInitialization::Uniform { low, high } => {
+                return self.bounds.clamp(rng.gen_range(low..high))
+            }
+            WeightInitialization::Xavier => (1.0 / fan_in).sqrt(),
+            WeightInitialization::He => (2.0 / fan_in).sqrt(),
+        };
+
+        self.normal_weight(standard_deviation, rng)
+    }
+
+    /// Samples the weight of a connection added by a mutation.
+    pub fn new_weight(&self, rng: &mut impl Rng) -> f64 {
+        self.normal_weight(self.standard_deviation, rng)
+    }
+
+    /// Samples from a normal distribution around zero, truncated to the bounds.
+    fn normal_weight(&self, standard_deviation: f64, rng: &mut impl Rng) -> f64 {
+        Connection::weight_perturbation_with(
+            0.0,
+            PerturbationDistribution::Normal,
+            standard_deviation,
+            BoundaryHandling::Resample,
+            &self.bounds,
+            rng,
+        )
+    }
+}
+
+/// Distribution initial connection weights are sampled from.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type")]
+#[serde(rename_all = "snake_case")]
+pub enum WeightInitialization {
+    /// Normal distribution around zero.
+    Normal { standard_deviation: f64 },
+    /// Uniform distribution in `[low, high)`.
+    Uniform { low: f64, high: f64 },
+    /// Normal distribution around zero with a standard deviation of `sqrt(1 / fan_in)`.
+    Xavier,
+    /// Normal distribution around zero with a standard deviation of `sqrt(2 / fan_in)`.
+    He,
+}
+
+/// Range connection weights are kept in.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type")]
+#[serde(rename_all = "snake_case")]
+pub enum WeightBounds {
+    /// Weights stay within `[min, max]`, the range is expected to contain zero.
+    Bounded { min: f64, max: f64 },
+    /// Weights can take any value.
+    Unbounded,
+}
+
+impl Default for WeightBounds {
+    fn default() -> Self {
+        Self::Bounded {
+            min: -1.0,
+            max: 1.0,
+        }
+    }
+}
+
+impl WeightBounds {
+    pub fn contains(&self, weight: f64) -> bool {
+        match *self {
+            Self::Bounded { min, max } => (min..=max).contains(&weight),
+            Self::Unbounded => true,
+        }
+    }
+
+    pub fn clamp(&self, weight: f64) -> f64 {
+        match *self {
+            Self::Bounded { min, max } => weight.clamp(min, max),
+            Self::Unbounded => weight,
+        }
+    }
+
+    /// The largest possible difference between two weights, if any.
+    pub fn maximum_difference(&self) -> Option<f64> {
+        match *self {
+            Self::Bounded { min, max } => Some(max - min),
+            Self::Unbounded => None,
+        }
+    }
+}
+
+/// Distribution weight perturbations are sampled from, see [`Mutations::change_weights_with`].
+///
+/// All distributions are centered around zero and scaled to the given standard deviation,
+/// except for [`PerturbationDistribution::Cauchy`] which has no finite variance and uses it as its scale instead.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PerturbationDistribution {
+    #[default]
+    Normal,
+    /// Heavy tailed, occasionally produces very large perturbations.
+    Cauchy,
+    Uniform,
+    /// Sharper peak and heavier tails than [`PerturbationDistribution::Normal`].
+    Laplace,
+}
+
+impl PerturbationDistribution {
+    pub fn sample(&self, standard_deviation: f64, rng: &mut impl Rng) -> f64 {
+        match self {
+            Self::Normal => rng.sample::<f64, _>(StandardNormal) * standard_deviation,
+            Self::Cauchy => {
+                Cauchy::new(0.0, 1.0).expect("valid scale").sample(rng) * standard_deviation
+            }
+            Self::Uniform => {
+                let half_width = 3.0_f64.sqrt() * standard_deviation;
+                rng.gen_range(-half_width..=half_width)
+            }
+            // the difference of two exponentially distributed values is laplace distributed
+            Self::Laplace => {
+                (rng.sample::<f64, _>(Exp1) - rng.sample::<f64, _>(Exp1)) * standard_deviation
+                    / 2.0_f64.sqrt()
+            }
+        }
+    }
+}
+
+/// How a weight that left its [`WeightBounds`] is brought back into them.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BoundaryHandling {
+    /// Sets the weight to the nearest bound, accumulating weights at the bounds.
+    Clamp,
+    /// Mirrors the weight at the bounds as often as needed.
+    #[default]
+    Reflect,
+    /// Samples again until the weight is in bounds, i.e. truncates the distribution.
+    /// Falls back to [`BoundaryHandling::Reflect`] should that take too many attempts.
+    Resample,
+    /// Lets the weight re-enter from the opposite bound.
+    Wrap,
+}
+
+impl BoundaryHandling {
+    const MAXIMUM_RESAMPLES: usize = 1000;
+
+    /// Draws a weight from `sample` and handles it leaving the `bounds`.
+    pub fn apply(&self, bounds: &WeightBounds, mut sample: impl FnMut() -> f64) -> f64 {
+        let weight = sample();
+
+        let (min, max) = match *bounds {
+            WeightBounds::Bounded { min, max } if !bounds.contains(weight) => (min, max),
+            _ => return weight,
+        };
+
+        let range = max - min;
+
+        if range <= 0.0 {
+            return min;
+        }
+
+        let reflect = |weight: f64| {
+            let offset = (weight - min).rem_euclid(2.0 * range);
+            min + if offset > range {
+                2.0 * range - offset
+            } else {
+                offset
+            }
+        };
+
+        match self {
+            Self::Clamp => weight.clamp(min, max),
+            Self::Reflect => reflect(weight),
+            Self::Resample => (0..Self::MAXIMUM_RESAMPLES)
+                .map(|_| sample())
+                .find(|&weight| bounds.contains(weight))
+                .unwrap_or_else(|| reflect(weight)),
+            Self::Wrap => min + (weight - min).rem_euclid(range),
+        }
+    }
+}
+
 impl Parameters {
-    pub fn new(path: &str) -> Result<Self, ConfigError> {
-        let mut s = Config::new();
+    /// JSON Schema of the config file format read by [`Parameters::new`], e.g. for editors to validate TOML configs.
+    ///
+    /// Serialize it with any serde format, e.g. `serde_json::to_string_pretty(&Parameters::json_schema())`.
+    #[cfg(feature = "schemars")]
+    pub fn json_schema() -> schemars::schema::RootSchema {
+        schemars::schema_for!(Parameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::{Config, File, FileFormat};
+    use rand::{rngs::SmallRng, thread_rng, SeedableRng};
+
+    use super::{
+        BoundaryHandling, CompatibilityFactors, InvalidValue, ParameterViolation, Parameters,
+        ParametersError, PerturbationDistribution, WeightBounds, WeightConfig,
+        WeightInitialization,
+    };
+    use crate::{Mutations, Normalization};
+
+    const SAMPLES: usize = 100_000;
+
+    fn perturbations(
+        distribution: PerturbationDistribution,
+        boundary_handling: BoundaryHandling,
+        bounds: WeightBounds,
+    ) -> Vec<f64> {
+        let rng = &mut SmallRng::seed_from_u64(42);
+
+        (0..SAMPLES)
+            .map(|_| boundary_handling.apply(&bounds, || distribution.sample(1.0, rng)))
+            .collect()
+    }
+
+    fn fraction(samples: &[f64], predicate: impl Fn(f64) -> bool) -> f64 {
+        samples.iter().filter(|&&sample| predicate(sample)).count() as f64 / samples.len() as f64
+    }
+
+    /// Mean, standard deviation and excess kurtosis.
+    fn moments(samples: &[f64]) -> (f64, f64, f64) {
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let central = |power: i32| samples.iter().map(|x| (x - mean).powi(power)).sum::<f64>() / n;
+        let variance = central(2);
+
+        (mean, variance.sqrt(), central(4) / variance.powi(2) - 3.0)
+    }
+
+    #[test]
+    fn read_weight_config() {
+        let mut config = Config::new();
+        config
+            .merge(File::from_str(
+                r#"
+                mutations = []
+
+                [structure]
+                number_of_inputs = 9
+                number_of_outputs = 2
+                percent_of_connected_inputs = 1.0
+                outputs_activation = "Tanh"
+                seed = 42
+
+                [weights]
+                standard_deviation = 0.5
+
+                [weights.initialization]
+                type = "uniform"
+                low = -0.5
+                high = 0.5
+
+                [weights.bounds]
+                type = "unbounded"
+                "#,
+                FileFormat::Toml,
+            ))
+            .unwrap();
+
+        let parameters: Parameters = config.try_into().unwrap();
+
+        assert_eq!(
+            parameters.weights,
+            WeightConfig {
+                initialization: WeightInitialization::Uniform {
+                    low: -0.5,
+                    high: 0.5
+                },
+                standard_deviation: 0.5,
+                bounds: WeightBounds::Unbounded,
+            }
+        );
+    }
+
+    #[test]
+    fn read_compatibility_factors() {
+        let mut config = Config::new();
+        config
+            .merge(File::from_str(
+                r#"
+                mutations = []
+
+                [structure]
+                number_of_inputs = 9
+                number_of_outputs = 2
+                percent_of_connected_inputs = 1.0
+                outputs_activation = "Tanh"
+                seed = 42
+
+                [compatibility]
+                recurrent_connections = 0.25
+                disjoint_nodes = 0.5
+                normalization = "larger_genome"
+                "#,
+                FileFormat::Toml,
+            ))
+            .unwrap();
+
+        let parameters: Parameters = config.try_into().unwrap();
+
+        assert_eq!(
+            parameters.compatibility,
+            CompatibilityFactors {
+                recurrent_connections: Some(0.25),
+                disjoint_nodes: 0.5,
+                normalization: Normalization::LargerGenome,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn reject_invalid_config_file() {
+        let path = std::env::temp_dir().join("set_genome_invalid_parameters.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [structure]
+            number_of_inputs = 2
+            number_of_outputs = 1
+            percent_of_connected_inputs = 1.0
+            outputs_activation = "Tanh"
+            seed = 42
+
+            [[mutations]]
+            type = "add_node"
+            chance = 0.1
+            activation_pool = []
+            "#,
+        )
+        .unwrap();
+
+        let result = Parameters::new(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        match result {
+            Err(ParametersError::Invalid(violations)) => assert_eq!(
+                violations,
+                vec![ParameterViolation::Mutation {
+                    index: 0,
+                    field: "activation_pool",
+                    value: InvalidValue::Empty,
+                }]
+            ),
+            other => panic!("expected invalid parameters, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn read_weight_mutations() {
+        let mut config = Config::new();
+        config
+            .merge(File::from_str(
+                r#"
+                [structure]
+                number_of_inputs = 9
+                number_of_outputs = 2
+                percent_of_connected_inputs = 1.0
+                outputs_activation = "Tanh"
+                seed = 42
+
+                [[mutations]]
+                type = "change_weights"
+                chance = 1.0
+                percent_perturbed = 0.5
+                standard_deviation = 0.1
+
+                [[mutations]]
+                type = "change_weights"
+                chance = 1.0
+                percent_perturbed = 0.5
+                standard_deviation = 0.1
+                distribution = "cauchy"
+                boundary_handling = "wrap"
+
+                [[mutations]]
+                type = "replace_weights"
+                chance = 0.1
+                percent_replaced = 0.2
+                "#,
+                FileFormat::Toml,
+            ))
+            .unwrap();
 
-        // Start off by merging in the "default" configuration file
-        s.merge(File::with_name(path))?;
+        let parameters: Parameters = config.try_into().unwrap();
 
-        // You can deserialize (and thus freeze) the entire configuration as
-        s.try_into()
+        assert_eq!(
+            parameters.mutations,
+            vec![
+                Mutations::ChangeWeights {
+                    chance: 1.0,
+                    percent_perturbed: 0.5,
+                    standard_deviation: 0.1,
+                    distribution: PerturbationDistribution::Normal,
+                    boundary_handling: BoundaryHandling::Reflect,
+                },
+                Mutations::ChangeWeights {
+                    chance: 1.0,
+                    percent_perturbed: 0.5,
+                    standard_deviation: 0.1,
+                    distribution: PerturbationDistribution::Cauchy,
+                    boundary_handling: BoundaryHandling::Wrap,
+                },
+                Mutations::ReplaceWeights {
+                    chance: 0.1,
+                    percent_replaced: 0.2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn weights_stay_within_bounds() {
+        let weights = WeightConfig {
+            initialization: WeightInitialization::He,
+            standard_deviation: 10.0,
+            bounds: WeightBounds::Bounded {
+                min: -0.5,
+                max: 0.25,
+            },
+        };
+
+        let rng = &mut thread_rng();
+
+        for _ in 0..1000 {
+            assert!(weights.bounds.contains(weights.new_weight(rng)));
+            assert!(weights.bounds.contains(weights.initial_weight(1, rng)));
+        }
+    }
+
+    #[test]
+    fn scale_by_fan_in() {
+        let weights = WeightConfig {
+            initialization: WeightInitialization::Xavier,
+            bounds: WeightBounds::Unbounded,
+            ..Default::default()
+        };
+
+        let rng = &mut thread_rng();
+
+        let variance = |fan_in: usize, rng: &mut _| {
+            (0..10000)
+                .map(|_| weights.initial_weight(fan_in, rng).powi(2))
+                .sum::<f64>()
+                / 10000.0
+        };
+
+        assert!((variance(1, rng) - 1.0).abs() < 0.1);
+        assert!((variance(100, rng) - 0.01).abs() < 0.001);
+    }
+
+    #[test]
+    fn perturbation_distributions_have_expected_shape() {
+        // expected excess kurtosis of the normal, uniform and laplace distribution
+        for (distribution, kurtosis) in [
+            (PerturbationDistribution::Normal, 0.0),
+            (PerturbationDistribution::Uniform, -1.2),
+            (PerturbationDistribution::Laplace, 3.0),
+        ] {
+            let (mean, standard_deviation, excess_kurtosis) = moments(&perturbations(
+                distribution,
+                BoundaryHandling::default(),
+                WeightBounds::Unbounded,
+            ));
+
+            assert!(mean.abs() < 0.02, "{:?}: mean {}", distribution, mean);
+            assert!(
+                (standard_deviation - 1.0).abs() < 0.02,
+                "{:?}: standard deviation {}",
+                distribution,
+                standard_deviation
+            );
+            assert!(
+                (excess_kurtosis - kurtosis).abs() < 0.3,
+                "{:?}: excess kurtosis {}",
+                distribution,
+                excess_kurtosis
+            );
+        }
+
+        // P(|x| < 1) for a standard normal distribution
+        let normal = perturbations(
+            PerturbationDistribution::Normal,
+            BoundaryHandling::default(),
+            WeightBounds::Unbounded,
+        );
+        assert!((fraction(&normal, |x| x.abs() < 1.0) - 0.6827).abs() < 0.01);
+    }
+
+    #[test]
+    fn cauchy_quartiles_match_scale() {
+        let samples = perturbations(
+            PerturbationDistribution::Cauchy,
+            BoundaryHandling::default(),
+            WeightBounds::Unbounded,
+        );
+
+        // the quartiles of a cauchy distribution are at -scale and +scale
+        assert!((fraction(&samples, |x| x < 0.0) - 0.5).abs() < 0.01);
+        assert!((fraction(&samples, |x| x < -1.0) - 0.25).abs() < 0.01);
+        assert!((fraction(&samples, |x| x < 1.0) - 0.75).abs() < 0.01);
+    }
+
+    #[test]
+    fn boundary_handling_maps_into_bounds() {
+        let bounds = WeightBounds::Bounded {
+            min: -1.0,
+            max: 1.0,
+        };
+
+        let apply = |boundary_handling: BoundaryHandling, weight: f64| {
+            boundary_handling.apply(&bounds, || weight)
+        };
+
+        assert!((apply(BoundaryHandling::Clamp, 1.5) - 1.0).abs() < f64::EPSILON);
+        assert!((apply(BoundaryHandling::Reflect, 1.5) - 0.5).abs() < f64::EPSILON);
+        assert!((apply(BoundaryHandling::Reflect, -3.5) - 0.5).abs() < f64::EPSILON);
+        assert!((apply(BoundaryHandling::Wrap, 1.5) + 0.5).abs() < f64::EPSILON);
+        assert!((apply(BoundaryHandling::Wrap, -3.5) - 0.5).abs() < f64::EPSILON);
+        // a sample that never enters the bounds ends up reflected
+        assert!((apply(BoundaryHandling::Resample, 1.5) - 0.5).abs() < f64::EPSILON);
+        // in bounds weights are untouched
+        assert!((apply(BoundaryHandling::Wrap, 0.3) - 0.3).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn boundary_handling_shapes_distribution() {
+        let bounds = WeightBounds::Bounded {
+            min: -1.0,
+            max: 1.0,
+        };
+
+        let samples = |boundary_handling| {
+            let samples =
+                perturbations(PerturbationDistribution::Normal, boundary_handling, bounds);
+            assert!(samples.iter().all(|&sample| bounds.contains(sample)));
+            samples
+        };
+
+        let central = |x: f64| x.abs() < 0.5;
+
+        // clamping moves all mass outside of the bounds onto them, P(|x| > 1) for a standard normal
+        let clamped = samples(BoundaryHandling::Clamp);
+        assert!((fraction(&clamped, |x| x.abs() == 1.0) - 0.3173).abs() < 0.01);
+
+        // resampling truncates, P(|x| < 0.5) / P(|x| < 1)
+        let resampled = samples(BoundaryHandling::Resample);
+        assert!(resampled.iter().all(|&sample| sample.abs() < 1.0));
+        assert!((fraction(&resampled, central) - 0.5609).abs() < 0.01);
+
+        // reflecting and wrapping both fold 1.5 < |x| < 2.5 onto the center, P(|x| < 0.5) + P(1.5 < |x| < 2.5)
+        for boundary_handling in [BoundaryHandling::Reflect, BoundaryHandling::Wrap] {
+            let folded = samples(boundary_handling);
+            assert!((fraction(&folded, central) - 0.5041).abs() < 0.01);
+        }
+
+        // ... but only reflecting keeps weights close to the bound they left
+        let rng = &mut SmallRng::seed_from_u64(42);
+        let mut near_opposite_bound = |boundary_handling: BoundaryHandling| {
+            let samples = (0..SAMPLES)
+                .map(|_| {
+                    boundary_handling.apply(&bounds, || {
+                        0.8 + PerturbationDistribution::Normal.sample(0.5, rng)
+                    })
+                })
+                .collect::<Vec<_>>();
+            fraction(&samples, |x| x < -0.9)
+        };
+        assert!(near_opposite_bound(BoundaryHandling::Reflect) < 0.001);
+        // P(1 < x < 1.1) for x ~ N(0.8, 0.5)
+        assert!((near_opposite_bound(BoundaryHandling::Wrap) - 0.0703).abs() < 0.01);
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn describe_config_format_as_json_schema() {
+        let schema = Parameters::json_schema();
+
+        let required = &schema.schema.object.as_ref().unwrap().required;
+        assert!(required.contains("structure"));
+        assert!(required.contains("mutations"));
+        assert!(!required.contains("compatibility"));
+
+        for definition in [
+            "Mutations",
+            "Activation",
+            "WeightConfig",
+            "PruneOptions",
+            "CompatibilityFactors",
+        ] {
+            assert!(
+                schema.definitions.contains_key(definition),
+                "{}",
+                definition
+            );
+        }
     }
 }
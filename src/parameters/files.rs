@@ -0,0 +1,234 @@
+#[cfg(feature = "save")]
+use std::{fs, io, path::Path};
+
+use config::{Config, ConfigError, Environment, File};
+use thiserror::Error;
+
+use crate::parameters::{ParameterViolation, Parameters};
+
+/// Reasons reading or writing [`Parameters`] can fail.
+#[derive(Error, Debug)]
+pub enum ParametersError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("The parameters are invalid: {}", joined(.0))]
+    Invalid(Vec<ParameterViolation>),
+    #[cfg(feature = "save")]
+    #[error("The file {0:?} has none of the extensions toml, json, yaml or yml.")]
+    UnsupportedFormat(String),
+    #[cfg(feature = "save")]
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[cfg(feature = "save")]
+    #[error(transparent)]
+    Toml(#[from] toml::ser::Error),
+    #[cfg(feature = "save")]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[cfg(feature = "save")]
+    #[error(transparent)]
+    Yaml(#[from] serde_yaml::Error),
+}
+
+fn joined(violations: &[ParameterViolation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl Parameters {
+    /// Prefix of environment variables overriding parameters in [`Parameters::layered`].
+    pub const ENVIRONMENT_PREFIX: &'static str = "SET_GENOME";
+
+    /// Reads the parameters from a config file and validates them, see [`Parameters::validate`].
+    pub fn new(path: &str) -> Result<Self, ParametersError> {
+        let mut s = Config::new();
+
+        // Start off by merging in the "default" configuration file
+        s.merge(File::with_name(path))?;
+
+        Self::from_config(s)
+    }
+
+    /// Merges the config files at `paths` in order, later files override single values of earlier ones, followed by overrides from the environment.
+    ///
+    /// Environment variables start with [`Parameters::ENVIRONMENT_PREFIX`] and separate nested keys by double underscores,
+    /// e.g. `SET_GENOME_STRUCTURE__SEED=7` sets `structure.seed`.
+    /// The result is validated, see [`Parameters::validate`].
+    ///
+    /// ```text
+    /// // let parameters = Parameters::layered(&["profiles/base.toml", "profiles/large.toml"]);
+    /// ```
+    pub fn layered(paths: &[&str]) -> Result<Self, ParametersError> {
+        Self::layered_with_prefix(paths, Self::ENVIRONMENT_PREFIX)
+    }
+
+    fn layered_with_prefix(paths: &[&str], prefix: &str) -> Result<Self, ParametersError> {
+        let mut s = Config::new();
+
+        for path in paths {
+            s.merge(File::with_name(path))?;
+        }
+
+        s.merge(
+            Environment::with_prefix(prefix)
+                .separator("__")
+                .ignore_empty(true),
+        )?;
+
+        Self::from_config(s)
+    }
+
+    /// Writes the parameters to `path` in the format given by its extension, one of `toml`, `json`, `yaml` or `yml`.
+    ///
+    /// The written file can be read again via [`Parameters::new`].
+    /// Requires the 'save' feature.
+    #[cfg(feature = "save")]
+    pub fn save(&self, path: &str) -> Result<(), ParametersError> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase);
+
+        let content = match extension.as_deref() {
+            // tables have to follow plain values in TOML, which `toml::Value` takes care of
+            Some("toml") => toml::to_string_pretty(&toml::Value::try_from(self)?)?,
+            Some("json") => serde_json::to_string_pretty(self)?,
+            Some("yaml") | Some("yml") => serde_yaml::to_string(self)?,
+            _ => return Err(ParametersError::UnsupportedFormat(path.to_owned())),
+        };
+
+        fs::write(path, content)?;
+
+        Ok(())
+    }
+
+    fn from_config(config: Config) -> Result<Self, ParametersError> {
+        // deserialize (and thus freeze) the merged configuration
+        let parameters: Self = config.try_into()?;
+
+        parameters.validate().map_err(ParametersError::Invalid)?;
+
+        Ok(parameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    #[cfg(feature = "save")]
+    use crate::PruneOptions;
+    use crate::{activations::Activation, Mutations, Parameters, ParametersError};
+
+    fn temporary(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("set_genome_{}_{}", std::process::id(), name))
+    }
+
+    #[cfg(feature = "save")]
+    #[test]
+    fn save_and_read_every_format() {
+        let mut parameters = Parameters::default();
+        parameters.mutations.push(Mutations::Prune {
+            chance: 0.01,
+            options: PruneOptions {
+                weight_threshold: 0.05,
+                remove_disabled: false,
+            },
+        });
+        parameters.compatibility.recurrent_connections = Some(0.25);
+
+        for extension in ["toml", "json", "yaml", "yml"] {
+            let path = temporary(&format!("parameters.{}", extension));
+            let path = path.to_str().unwrap();
+
+            parameters.save(path).unwrap();
+            let read = Parameters::new(path);
+            fs::remove_file(path).unwrap();
+
+            assert_eq!(read.unwrap(), parameters, "{}", extension);
+        }
+
+        assert!(matches!(
+            parameters.save("parameters.txt"),
+            Err(ParametersError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn print_every_violation() {
+        let mut parameters = Parameters::basic(2, 0);
+        parameters
+            .mutations
+            .push(Mutations::AddConnection { chance: 3.0 });
+
+        let error = ParametersError::Invalid(parameters.validate().unwrap_err());
+
+        assert_eq!(
+            error.to_string(),
+            "The parameters are invalid: structure.number_of_outputs must be at least one; mutations[3].chance must be within [0, 1] but is 3"
+        );
+    }
+
+    #[test]
+    fn override_layers_and_environment() {
+        let base = temporary("base.toml");
+        let run = temporary("run.json");
+
+        fs::write(
+            &base,
+            r#"
+[structure]
+number_of_inputs = 3
+number_of_outputs = 2
+percent_of_connected_inputs = 1.0
+outputs_activation = "Tanh"
+seed = 42
+
+[weights]
+standard_deviation = 0.1
+initialization = { type = "normal", standard_deviation = 0.1 }
+bounds = { type = "unbounded" }
+
+[[mutations]]
+type = "add_node"
+chance = 0.01
+activation_pool = ["Tanh"]
+"#,
+        )
+        .unwrap();
+        fs::write(
+            &run,
+            r#"{ "structure": { "number_of_outputs": 4 }, "weights": { "standard_deviation": 0.5 } }"#,
+        )
+        .unwrap();
+
+        // a prefix of its own keeps the override away from other tests and processes
+        let prefix = format!("TEST_SET_GENOME_{}", std::process::id());
+        let seed = format!("{}_STRUCTURE__SEED", prefix);
+        env::set_var(&seed, "7");
+        let parameters = Parameters::layered_with_prefix(
+            &[base.to_str().unwrap(), run.to_str().unwrap()],
+            &prefix,
+        );
+        env::remove_var(&seed);
+        fs::remove_file(&base).unwrap();
+        fs::remove_file(&run).unwrap();
+
+        let parameters = parameters.unwrap();
+
+        assert_eq!(parameters.structure.number_of_inputs, 3);
+        assert_eq!(parameters.structure.number_of_outputs, 4);
+        assert_eq!(parameters.structure.seed, 7);
+        assert_eq!(parameters.weights.standard_deviation, 0.5);
+        assert_eq!(
+            parameters.mutations[0],
+            Mutations::AddNode {
+                chance: 0.01,
+                activation_pool: vec![Activation::Tanh],
+            }
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use thiserror::Error;
+
+use crate::{
+    genome::CompatibilityFactors,
+    mutations::Mutations,
+    parameters::{Parameters, WeightBounds, WeightInitialization},
+};
+
+/// Why a value is not acceptable, see [`ParameterViolation`].
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum InvalidValue {
+    #[error("must be within [0, 1] but is {0}")]
+    NotAProbability(f64),
+    #[error("must be a finite, non-negative number but is {0}")]
+    Negative(f64),
+    #[error("must be at least one")]
+    Zero,
+    #[error("must not be empty")]
+    Empty,
+    #[error("must describe a non-empty range but is [{low}, {high}]")]
+    EmptyRange { low: f64, high: f64 },
+}
+
+/// A value in the [`Parameters`] that would lead to panics or meaningless results, see [`Parameters::validate`].
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ParameterViolation {
+    #[error("structure.{field} {value}")]
+    Structure {
+        field: &'static str,
+        value: InvalidValue,
+    },
+    #[error("mutations[{index}].{field} {value}")]
+    Mutation {
+        index: usize,
+        field: &'static str,
+        value: InvalidValue,
+    },
+    #[error("weights.{field} {value}")]
+    Weights {
+        field: &'static str,
+        value: InvalidValue,
+    },
+    #[error("compatibility.{field} {value}")]
+    Compatibility {
+        field: &'static str,
+        value: InvalidValue,
+    },
+}
+
+fn probability(value: f64) -> Result<(), InvalidValue> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(())
+    } else {
+        Err(InvalidValue::NotAProbability(value))
+    }
+}
+
+fn non_negative(value: f64) -> Result<(), InvalidValue> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(InvalidValue::Negative(value))
+    }
+}
+
+fn non_empty<T>(values: &[T]) -> Result<(), InvalidValue> {
+    if values.is_empty() {
+        Err(InvalidValue::Empty)
+    } else {
+        Ok(())
+    }
+}
+
+fn range(low: f64, high: f64) -> Result<(), InvalidValue> {
+    if low < high {
+        Ok(())
+    } else {
+        Err(InvalidValue::EmptyRange { low, high })
+    }
+}
+
+impl Parameters {
+    /// Checks every value for its valid range and lists all violations found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use set_genome::{InvalidValue, Mutations, ParameterViolation, Parameters};
+    ///
+    /// let mut parameters = Parameters::basic(2, 1);
+    ///
+    /// assert_eq!(parameters.validate(), Ok(()));
+    ///
+    /// parameters.mutations.push(Mutations::AddConnection { chance: 3.0 });
+    ///
+    /// let violations = parameters.validate().unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     violations,
+    ///     vec![ParameterViolation::Mutation {
+    ///         index: 3,
+    ///         field: "chance",
+    ///         value: InvalidValue::NotAProbability(3.0),
+    ///     }]
+    /// );
+    /// assert_eq!(violations[0].to_string(), "mutations[3].chance must be within [0, 1] but is 3");
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<ParameterViolation>> {
+        let mut violations = Vec::new();
+
+        let mut structure = |field, check: Result<(), InvalidValue>| {
+            if let Err(value) = check {
+                violations.push(ParameterViolation::Structure { field, value });
+            }
+        };
+        if self.structure.number_of_outputs == 0 {
+            structure("number_of_outputs", Err(InvalidValue::Zero));
+        }
+        structure(
+            "percent_of_connected_inputs",
+            probability(self.structure.percent_of_connected_inputs),
+        );
+
+        for (index, mutation) in self.mutations.iter().enumerate() {
+            let mut check = |field, check: Result<(), InvalidValue>| {
+                if let Err(value) = check {
+                    violations.push(ParameterViolation::Mutation {
+                        index,
+                        field,
+                        value,
+                    });
+                }
+            };
+
+            check("chance", probability(mutation.chance()));
+
+            match mutation {
+                Mutations::ChangeWeights {
+                    percent_perturbed,
+                    standard_deviation,
+                    ..
+                } => {
+                    check("percent_perturbed", probability(*percent_perturbed));
+                    check("standard_deviation", non_negative(*standard_deviation));
+                }
+                Mutations::ReplaceWeights {
+                    percent_replaced, ..
+                } => {
+                    check("percent_replaced", probability(*percent_replaced));
+                }
+                Mutations::ChangeActivation {
+                    activation_pool, ..
+                }
+                | Mutations::AddNode {
+                    activation_pool, ..
+                } => {
+                    check("activation_pool", non_empty(activation_pool));
+                }
+                Mutations::ChangeNodeParameters {
+                    percent_perturbed,
+                    bias_standard_deviation,
+                    response_standard_deviation,
+                    ..
+                } => {
+                    check("percent_perturbed", probability(*percent_perturbed));
+                    check(
+                        "bias_standard_deviation",
+                        non_negative(*bias_standard_deviation),
+                    );
+                    check(
+                        "response_standard_deviation",
+                        non_negative(*response_standard_deviation),
+                    );
+                }
+                Mutations::Prune { options, .. } => {
+                    check(
+                        "options.weight_threshold",
+                        non_negative(options.weight_threshold),
+                    );
+                }
+                Mutations::AddConnection { .. }
+                | Mutations::AddRecurrentConnection { .. }
+                | Mutations::RemoveNode { .. }
+                | Mutations::RemoveConnection { .. }
+                | Mutations::RemoveRecurrentConnection { .. }
+                | Mutations::DuplicateNode { .. }
+                | Mutations::ToggleConnection { .. }
+                | Mutations::ReEnableConnection { .. } => {}
+            }
+        }
+
+        let mut weight_config = |field, check: Result<(), InvalidValue>| {
+            if let Err(value) = check {
+                violations.push(ParameterViolation::Weights { field, value });
+            }
+        };
+        match self.weights.initialization {
+            WeightInitialization::Normal { standard_deviation } => weight_config(
+                "initialization.standard_deviation",
+                non_negative(standard_deviation),
+            ),
+            WeightInitialization::Uniform { low, high } => {
+                weight_config("initialization", range(low, high))
+            }
+            WeightInitialization::Xavier | WeightInitialization::He => {}
+        }
+        weight_config(
+            "standard_deviation",
+            non_negative(self.weights.standard_deviation),
+        );
+        if let WeightBounds::Bounded { min, max } = self.weights.bounds {
+            weight_config("bounds", range(min, max));
+        }
+
+        let CompatibilityFactors {
+            connections,
+            recurrent_connections,
+            disjoint_nodes,
+            weights,
+            activations,
+            node_parameters,
+            ..
+        } = self.compatibility;
+        for (field, factor) in [
+            ("connections", Some(connections)),
+            ("recurrent_connections", recurrent_connections),
+            ("disjoint_nodes", Some(disjoint_nodes)),
+            ("weights", Some(weights)),
+            ("activations", Some(activations)),
+            ("node_parameters", Some(node_parameters)),
+        ] {
+            if let Some(Err(value)) = factor.map(non_negative) {
+                violations.push(ParameterViolation::Compatibility { field, value });
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        activations::Activation, InvalidValue, Mutations, ParameterViolation, Parameters,
+        WeightBounds, WeightInitialization,
+    };
+
+    #[test]
+    fn default_parameters_are_valid() {
+        assert_eq!(Parameters::default().validate(), Ok(()));
+        assert_eq!(Parameters::basic(3, 2).validate(), Ok(()));
+    }
+
+    #[test]
+    fn report_every_violation() {
+        let mut parameters = Parameters::basic(3, 0);
+        parameters.mutations = vec![
+            Mutations::AddConnection { chance: 1.0 },
+            Mutations::ChangeWeights {
+                chance: 1.0,
+                percent_perturbed: -1.0,
+                standard_deviation: f64::NAN,
+                distribution: Default::default(),
+                boundary_handling: Default::default(),
+            },
+            Mutations::AddNode {
+                chance: 3.0,
+                activation_pool: Vec::<Activation>::new(),
+            },
+        ];
+        parameters.weights.initialization = WeightInitialization::Uniform {
+            low: 0.5,
+            high: -0.5,
+        };
+        parameters.weights.bounds = WeightBounds::Bounded { min: 1.0, max: 1.0 };
+        parameters.compatibility.recurrent_connections = Some(-1.0);
+
+        let violations = parameters.validate().unwrap_err();
+
+        assert_eq!(violations.len(), 8);
+        assert_eq!(
+            violations[0],
+            ParameterViolation::Structure {
+                field: "number_of_outputs",
+                value: InvalidValue::Zero
+            }
+        );
+        assert_eq!(
+            violations[1],
+            ParameterViolation::Mutation {
+                index: 1,
+                field: "percent_perturbed",
+                value: InvalidValue::NotAProbability(-1.0)
+            }
+        );
+        assert!(matches!(
+            violations[2],
+            ParameterViolation::Mutation {
+                index: 1,
+                field: "standard_deviation",
+                value: InvalidValue::Negative(_)
+            }
+        ));
+        assert_eq!(
+            violations[3..5],
+            [
+                ParameterViolation::Mutation {
+                    index: 2,
+                    field: "chance",
+                    value: InvalidValue::NotAProbability(3.0)
+                },
+                ParameterViolation::Mutation {
+                    index: 2,
+                    field: "activation_pool",
+                    value: InvalidValue::Empty
+                }
+            ]
+        );
+        assert_eq!(
+            violations[5].to_string(),
+            "weights.initialization must describe a non-empty range but is [0.5, -0.5]"
+        );
+        assert_eq!(
+            violations[6].to_string(),
+            "weights.bounds must describe a non-empty range but is [1, 1]"
+        );
+        assert_eq!(
+            violations[7].to_string(),
+            "compatibility.recurrent_connections must be a finite, non-negative number but is -1"
+        );
+    }
+}
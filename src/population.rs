@@ -0,0 +1,526 @@
+//! A minimal generational loop around [`Genome`], in the spirit of [NEAT].
+//!
+//! Genomes are grouped into [`Species`] via the [`CompatibilityDistance`] configured in [`Parameters::compatibility`], each species receives offspring proportional to its average fitness
+//! and offspring are produced by [crossover] of two members followed by [mutation].
+//!
+//! [NEAT]: http://nn.cs.utexas.edu/downloads/papers/stanley.ec02.pdf
+//! [crossover]: `Genome::cross_in_with_rng`
+//! [mutation]: `Genome::mutate_with_rng`
+
+use std::cmp::Ordering;
+
+use rand::{rngs::SmallRng, seq::SliceRandom, thread_rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{CompatibilityDistance, CrossoverStrategy, Genome, Id, IdRegistry, Parameters};
+
+/// Configuration of the [`Population`] in addition to the genome [`Parameters`].
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PopulationParameters {
+    /// Number of genomes per generation.
+    pub size: usize,
+    /// Number of species the compatibility threshold is adjusted towards.
+    pub target_species: usize,
+    /// Compatibility threshold used for the first generation.
+    pub initial_threshold: f64,
+    /// Amount the compatibility threshold changes per generation while the number of species differs from the target.
+    pub threshold_step: f64,
+    /// Percent of the fittest members of every species that are allowed to reproduce, at least one member always is.
+    pub survival_rate: f64,
+    /// How offspring combines its parents, see [`Genome::cross_in_with_strategy`].
+    #[serde(default)]
+    pub crossover: CrossoverStrategy,
+}
+
+impl Default for PopulationParameters {
+    fn default() -> Self {
+        Self {
+            size: 150,
+            target_species: 10,
+            initial_threshold: 0.3,
+            threshold_step: 0.01,
+            survival_rate: 0.2,
+            crossover: CrossoverStrategy::default(),
+        }
+    }
+}
+
+/// A genome together with its fitness.
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub genome: Genome,
+    pub fitness: f64,
+}
+
+/// A group of similar members, identified by their indices into [`Population::members`].
+#[derive(Debug, Clone)]
+pub struct Species {
+    /// Genome new members are compared against.
+    pub representative: Genome,
+    pub members: Vec<usize>,
+}
+
+/// Holds a generation of genomes and evolves it via speciation, crossover and mutation.
+///
+/// # Example
+///
+/// ```
+/// use set_genome::{Genome, Parameters, Population, PopulationParameters};
+///
+/// let mut population = Population::with_seed(
+///     Parameters::basic(2, 1),
+///     PopulationParameters {
+///         size: 20,
+///         ..Default::default()
+///     },
+///     42,
+/// );
+///
+/// // the fitness closure can be anything, here smaller genomes are considered fitter
+/// for _ in 0..5 {
+///     population.evolve(|genome: &Genome| 1.0 / (1.0 + genome.len() as f64));
+/// }
+///
+/// assert_eq!(population.generation, 5);
+/// assert_eq!(population.members.len(), 20);
+/// ```
+pub struct Population {
+    pub parameters: Parameters,
+    pub population_parameters: PopulationParameters,
+    pub members: Vec<Member>,
+    pub species: Vec<Species>,
+    /// Current compatibility threshold, adjusted each generation towards [`PopulationParameters::target_species`].
+    pub compatibility_threshold: f64,
+    /// Number of completed calls to [`Population::evolve`].
+    pub generation: usize,
+    champion: Option<Member>,
+    rng: SmallRng,
+}
+
+impl Population {
+    /// Creates a population of initialized genomes.
+    pub fn new(parameters: Parameters, population_parameters: PopulationParameters) -> Self {
+        Self::with_rng(
+            parameters,
+            population_parameters,
+            SmallRng::from_rng(thread_rng()).unwrap(),
+        )
+    }
+
+    /// Same as [`Population::new`] but every generation is reproducible from the given `seed`.
+    pub fn with_seed(
+        parameters: Parameters,
+        population_parameters: PopulationParameters,
+        seed: u64,
+    ) -> Self {
+        Self::with_rng(
+            parameters,
+            population_parameters,
+            SmallRng::seed_from_u64(seed),
+        )
+    }
+
+    fn with_rng(
+        parameters: Parameters,
+        population_parameters: PopulationParameters,
+        mut rng: SmallRng,
+    ) -> Self {
+        let members = (0..population_parameters.size)
+            .map(|_| Member {
+                genome: Genome::initialized_with_rng(&parameters, &mut rng),
+                fitness: 0.0,
+            })
+            .collect();
+
+        Self {
+            compatibility_threshold: population_parameters.initial_threshold,
+            parameters,
+            population_parameters,
+            members,
+            species: Vec::new(),
+            generation: 0,
+            champion: None,
+            rng,
+        }
+    }
+
+    /// The fittest member seen in any evaluated generation so far.
+    pub fn champion(&self) -> Option<&Member> {
+        self.champion.as_ref()
+    }
+
+    /// Observes every member under its index into [`Population::members`], see [`IdRegistry::observe`].
+    ///
+    /// Carriers registered for earlier generations are forgotten, as indices are reused.
+    /// Returns the sorted ids whose origin could not be resolved.
+    pub fn register_ids(&self, registry: &mut IdRegistry) -> Vec<Id> {
+        registry.clear_carriers();
+
+        let mut unknown = self
+            .members
+            .iter()
+            .enumerate()
+            .flat_map(|(index, member)| registry.observe(index, &member.genome))
+            .collect::<Vec<_>>();
+        unknown.sort_unstable();
+        unknown.dedup();
+        unknown
+    }
+
+    /// Runs one generation: evaluates all members with `fitness`, speciates them and replaces them with their offspring.
+    ///
+    /// Fitness is expected to be non-negative, negative values are treated as zero when allocating offspring.
+    /// NaN is considered the lowest fitness.
+    pub fn evolve(&mut self, fitness: impl FnMut(&Genome) -> f64) {
+        self.evaluate(fitness);
+        self.speciate();
+        self.reproduce();
+        self.generation += 1;
+    }
+
+    /// Assigns a fitness to every member and updates the champion.
+    pub fn evaluate(&mut self, mut fitness: impl FnMut(&Genome) -> f64) {
+        for member in &mut self.members {
+            member.fitness = fitness(&member.genome);
+        }
+
+        if let Some(best) = self
+            .members
+            .iter()
+            .max_by(|a, b| by_fitness(a.fitness, b.fitness))
+        {
+            let improved = match &self.champion {
+                Some(champion) => by_fitness(best.fitness, champion.fitness) == Ordering::Greater,
+                None => true,
+            };
+            if improved {
+                self.champion = Some(best.clone());
+            }
+        }
+    }
+
+    /// Assigns every member to the first species whose representative is within the compatibility threshold, or founds a new species.
+    /// Afterwards the threshold is adjusted towards the targeted number of species.
+    pub fn speciate(&mut self) {
+        let distance = CompatibilityDistance::from_parameters(&self.parameters);
+
+        for species in &mut self.species {
+            species.members.clear();
+        }
+
+        let threshold = self.compatibility_threshold;
+
+        for (index, member) in self.members.iter().enumerate() {
+            if let Some(species) = self.species.iter_mut().find(|species| {
+                distance.between(&member.genome, &species.representative) < threshold
+            }) {
+                species.members.push(index);
+            } else {
+                self.species.push(Species {
+                    representative: member.genome.clone(),
+                    members: vec![index],
+                });
+            }
+        }
+
+        self.species.retain(|species| !species.members.is_empty());
+
+        // order members by fitness and let the fittest represent the species in the next generation
+        for species in &mut self.species {
+            let members = &self.members;
+            species
+                .members
+                .sort_by(|&a, &b| by_fitness(members[b].fitness, members[a].fitness));
+            species.representative = members[species.members[0]].genome.clone();
+        }
+
+        let PopulationParameters {
+            target_species,
+            threshold_step,
+            ..
+        } = self.population_parameters;
+
+        if self.species.len() < target_species {
+            self.compatibility_threshold =
+                (self.compatibility_threshold - threshold_step).max(threshold_step);
+        } else if self.species.len() > target_species {
+            self.compatibility_threshold += threshold_step;
+        }
+    }
+
+    /// Number of offspring per species, proportional to the average fitness of its members, see [`Population::speciate`].
+    pub fn offspring_allocation(&self) -> Vec<usize> {
+        let size = self.population_parameters.size;
+
+        let average_fitness = self
+            .species
+            .iter()
+            .map(|species| {
+                if species.members.is_empty() {
+                    return 0.0;
+                }
+                species
+                    .members
+                    .iter()
+                    .map(|&index| self.members[index].fitness.max(0.0))
+                    .sum::<f64>()
+                    / species.members.len() as f64
+            })
+            .collect::<Vec<_>>();
+
+        let total_fitness = average_fitness.iter().sum::<f64>();
+
+        let shares = average_fitness
+            .iter()
+            .map(|&fitness| {
+                if total_fitness > 0.0 {
+                    fitness / total_fitness * size as f64
+                } else {
+                    size as f64 / self.species.len() as f64
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut allocation = shares
+            .iter()
+            .map(|share| share.floor() as usize)
+            .collect::<Vec<_>>();
+
+        // hand out the remaining offspring by largest remainder
+        let mut by_remainder = (0..shares.len()).collect::<Vec<_>>();
+        by_remainder.sort_by(|&a, &b| {
+            (shares[b] - shares[b].floor()).total_cmp(&(shares[a] - shares[a].floor()))
+        });
+
+        let missing = size - allocation.iter().sum::<usize>();
+        for &index in by_remainder.iter().take(missing) {
+            allocation[index] += 1;
+        }
+
+        allocation
+    }
+
+    /// Replaces all members with offspring of the current species, the members are speciated first if no species holds any members.
+    ///
+    /// Each species keeps its fittest member unchanged, all other offspring are produced by crossing two surviving members (fitter parent first) and mutating the result.
+    /// Afterwards the species only keep their representative, as their members referred to the replaced generation.
+    pub fn reproduce(&mut self) {
+        if self
+            .species
+            .iter()
+            .all(|species| species.members.is_empty())
+        {
+            self.speciate();
+        }
+
+        let allocation = self.offspring_allocation();
+        let mut offspring = Vec::with_capacity(self.population_parameters.size);
+
+        for (species, amount) in self.species.iter().zip(allocation) {
+            if amount == 0 {
+                continue;
+            }
+
+            let survivors = ((species.members.len() as f64
+                * self.population_parameters.survival_rate)
+                .ceil() as usize)
+                .max(1);
+            let parents = &species.members[..survivors];
+
+            // elitism
+            offspring.push(Member {
+                genome: self.members[parents[0]].genome.clone(),
+                fitness: 0.0,
+            });
+
+            for _ in 1..amount {
+                let mut pair = [
+                    *parents.choose(&mut self.rng).unwrap(),
+                    *parents.choose(&mut self.rng).unwrap(),
+                ];
+                // parents are ordered by fitness, so the smaller index is the fitter one
+                pair.sort_unstable_by_key(|&index| {
+                    parents.iter().position(|&parent| parent == index)
+                });
+
+                let mut genome = self.members[pair[0]].genome.cross_in_with_strategy(
+                    &self.members[pair[1]].genome,
+                    &self.population_parameters.crossover,
+                    &mut self.rng,
+                );
+
+                // failing mutations are not fatal for reproduction
+                let _ = genome.mutate_with_rng(&self.parameters, &mut self.rng);
+
+                offspring.push(Member {
+                    genome,
+                    fitness: 0.0,
+                });
+            }
+        }
+
+        self.members = offspring;
+
+        for species in &mut self.species {
+            species.members.clear();
+        }
+    }
+}
+
+/// Orders fitness values ascending, NaN is considered lower than any other fitness.
+fn by_fitness(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b)
+        .unwrap_or_else(|| b.is_nan().cmp(&a.is_nan()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Population, PopulationParameters};
+    use crate::{Genome, IdRegistry, Parameters};
+
+    #[test]
+    fn offspring_allocation_fills_population() {
+        let mut parameters = Parameters::basic(5, 2);
+        // connect different inputs to get distinct species
+        parameters.structure.percent_of_connected_inputs = 0.4;
+
+        let mut population = Population::with_seed(
+            parameters,
+            PopulationParameters {
+                size: 37,
+                initial_threshold: 0.05,
+                ..Default::default()
+            },
+            42,
+        );
+
+        population
+            .evaluate(|genome: &Genome| genome.feed_forward.iter().map(|c| c.weight).sum::<f64>());
+        population.speciate();
+
+        assert!(population.species.len() > 1);
+        assert_eq!(population.offspring_allocation().iter().sum::<usize>(), 37);
+
+        population.reproduce();
+
+        assert_eq!(population.members.len(), 37);
+    }
+
+    #[test]
+    fn reproduce_without_speciation() {
+        let mut population = Population::with_seed(
+            Parameters::basic(3, 2),
+            PopulationParameters {
+                size: 10,
+                ..Default::default()
+            },
+            42,
+        );
+
+        population.reproduce();
+
+        assert!(!population.species.is_empty());
+        assert_eq!(population.members.len(), 10);
+
+        // the species of the previous generation are not reused
+        population.members.truncate(3);
+        population.reproduce();
+
+        assert_eq!(population.members.len(), 10);
+    }
+
+    #[test]
+    fn nan_fitness_is_lowest() {
+        let mut population = Population::with_seed(
+            Parameters::basic(3, 2),
+            PopulationParameters {
+                size: 10,
+                ..Default::default()
+            },
+            42,
+        );
+
+        let mut count = 0;
+        population.evolve(|_: &Genome| {
+            count += 1;
+            if count == 1 {
+                1.0
+            } else {
+                f64::NAN
+            }
+        });
+
+        assert_eq!(population.champion().unwrap().fitness, 1.0);
+        assert_eq!(population.members.len(), 10);
+    }
+
+    #[test]
+    fn threshold_moves_towards_target() {
+        let mut population = Population::with_seed(
+            Parameters::basic(3, 2),
+            PopulationParameters {
+                size: 10,
+                target_species: 100,
+                ..Default::default()
+            },
+            42,
+        );
+
+        let threshold = population.compatibility_threshold;
+
+        population.evolve(|_: &Genome| 1.0);
+
+        assert!(population.compatibility_threshold < threshold);
+    }
+
+    #[test]
+    fn same_seed_same_population() {
+        let run = || {
+            let mut population = Population::with_seed(
+                Parameters::basic(3, 2),
+                PopulationParameters {
+                    size: 20,
+                    ..Default::default()
+                },
+                7,
+            );
+
+            for _ in 0..10 {
+                population.evolve(|genome: &Genome| genome.len() as f64);
+            }
+
+            population
+                .members
+                .into_iter()
+                .map(|member| member.genome)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn register_ids_of_all_members() {
+        let mut population = Population::with_seed(
+            Parameters::default(),
+            PopulationParameters {
+                size: 20,
+                ..Default::default()
+            },
+            42,
+        );
+        let mut registry = IdRegistry::default();
+
+        for _ in 0..10 {
+            population.evolve(|genome: &Genome| genome.len() as f64);
+            assert!(population.register_ids(&mut registry).is_empty());
+        }
+
+        for (index, member) in population.members.iter().enumerate() {
+            for node in member.genome.hidden.iter() {
+                assert!(registry.origin(node.id).is_some());
+                assert!(registry.sharing_innovation(node.id).contains(&index));
+            }
+        }
+    }
+}
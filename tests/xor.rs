@@ -0,0 +1,103 @@
+#![cfg(feature = "population")]
+
+use set_genome::{
+    activations::Activation, BoundaryHandling, Genome, Mutations, Parameters,
+    PerturbationDistribution, Population, PopulationParameters, Structure,
+};
+
+const XOR: [([f64; 2], f64); 4] = [
+    ([0.0, 0.0], 0.0),
+    ([0.0, 1.0], 1.0),
+    ([1.0, 0.0], 1.0),
+    ([1.0, 1.0], 0.0),
+];
+
+fn error(genome: &Genome) -> f64 {
+    let mut network = genome.compile().expect("valid genome");
+
+    XOR.iter()
+        .map(|(input, expected)| {
+            network.reset();
+            (network.evaluate(input)[0] - expected).abs()
+        })
+        .sum()
+}
+
+fn solves_xor(genome: &Genome) -> bool {
+    let mut network = genome.compile().expect("valid genome");
+
+    XOR.iter().all(|(input, expected)| {
+        network.reset();
+        (network.evaluate(input)[0] - expected).abs() < 0.5
+    })
+}
+
+#[test]
+fn evolve_xor() {
+    // weights are capped to [-1, 1], so also offer activations that can express XOR with small weights
+    let activation_pool = vec![
+        Activation::Sigmoid,
+        Activation::Tanh,
+        Activation::Gaussian,
+        Activation::Absolute,
+        Activation::Relu,
+    ];
+
+    let parameters = Parameters {
+        structure: Structure {
+            number_of_inputs: 2,
+            number_of_outputs: 1,
+            percent_of_connected_inputs: 1.0,
+            outputs_activation: Activation::Sigmoid,
+            seed: 42,
+            bias: true,
+        },
+        mutations: vec![
+            Mutations::ChangeWeights {
+                chance: 0.8,
+                percent_perturbed: 0.5,
+                standard_deviation: 0.5,
+                distribution: PerturbationDistribution::Normal,
+                boundary_handling: BoundaryHandling::Reflect,
+            },
+            Mutations::AddNode {
+                chance: 0.03,
+                activation_pool: activation_pool.clone(),
+            },
+            Mutations::ChangeActivation {
+                chance: 0.05,
+                activation_pool,
+            },
+            Mutations::AddConnection { chance: 0.05 },
+        ],
+        ..Default::default()
+    };
+
+    let mut population = Population::with_seed(
+        parameters,
+        PopulationParameters {
+            size: 150,
+            target_species: 5,
+            ..Default::default()
+        },
+        42,
+    );
+
+    for _ in 0..300 {
+        // NEAT's original fitness measure
+        population.evolve(|genome: &Genome| (4.0 - error(genome)).powi(2));
+
+        if solves_xor(&population.champion().unwrap().genome) {
+            break;
+        }
+    }
+
+    let champion = &population.champion().unwrap().genome;
+
+    assert!(
+        solves_xor(champion),
+        "no solution after {} generations, best genome: {}",
+        population.generation,
+        Genome::dot(champion)
+    );
+}